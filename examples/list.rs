@@ -1,9 +1,15 @@
 extern crate ddc;
 extern crate ddc_macos;
 
-use ddc::Ddc;
-use ddc_macos::Monitor;
+#[cfg(target_os = "macos")]
+use ddc_macos::{Monitor, VcpFeature};
 
+#[cfg(not(target_os = "macos"))]
+fn main() {
+    println!("Listing monitors is only supported on MacOS");
+}
+
+#[cfg(target_os = "macos")]
 fn main() {
     let monitors = Monitor::enumerate().expect("Could not enumerate external monitors");
 
@@ -21,8 +27,8 @@ fn main() {
         if let Some(number) = monitor.serial_number() {
             println!("\tSerial Number: {}", number);
         }
-        if let Ok(input) = monitor.get_vcp_feature(0x60) {
-            println!("\tCurrent input: {:04x}", input.value());
+        if let Ok(input) = monitor.input_source() {
+            println!("\tCurrent input: {}", input);
         }
         if let Some(data) = monitor.edid() {
             let mut cursor = std::io::Cursor::new(&data);
@@ -33,12 +39,12 @@ fn main() {
             }
         }
 
-        match monitor.get_vcp_feature(0x10) {
+        match monitor.get_feature(VcpFeature::Luminance) {
             Ok(value) => println!("\tvcp info: {:?}", value),
             Err(e) => println!("\tFailed to set brightness: {}", e),
         }
         
-        match monitor.set_vcp_feature(0x10, 0) {
+        match monitor.set_feature(VcpFeature::Luminance, 0) {
             Ok(_) => println!("\tset brightness to 0"),
             Err(e) => println!("\tFailed to set brightness: {}", e),
         }
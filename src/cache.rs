@@ -0,0 +1,133 @@
+use crate::edid::Edid;
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+
+/// Identifies a monitor model and unit in a [CapabilitiesCache]
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct CacheKey {
+    /// Three-letter PNP ID of the manufacturer
+    pub vendor: String,
+    /// Manufacturer product code
+    pub product: u16,
+    /// Serial number: the serial number string of the EDID if any, the numeric serial number otherwise
+    pub serial: String,
+}
+
+impl CacheKey {
+    /// Key of the monitor described by raw EDID data, `None` if the data cannot be parsed
+    pub fn from_edid(edid: &[u8]) -> Option<Self> {
+        let edid = Edid::parse(edid).ok()?;
+        let serial = match edid.serial_string() {
+            Some(serial) if !serial.is_empty() => serial.to_string(),
+            _ => edid.serial_number.to_string(),
+        };
+        Some(CacheKey {
+            vendor: edid.manufacturer,
+            product: edid.product_code,
+            serial,
+        })
+    }
+
+    /// Name of the cache file for this key. Characters that are not safe in file names are replaced.
+    fn file_name(&self) -> String {
+        let safe = |text: &str| -> String {
+            text.chars()
+                .map(|c| if c.is_ascii_alphanumeric() || c == '-' { c } else { '_' })
+                .collect()
+        };
+        format!(
+            "{}-{:04x}-{}.caps",
+            safe(&self.vendor),
+            self.product,
+            safe(&self.serial)
+        )
+    }
+}
+
+/// An on-disk cache of monitor capabilities strings.
+///
+/// Reading the capabilities string of a monitor takes several seconds, as it is transferred in 32-byte fragments.
+/// The cache keeps the raw string of each monitor in a file of its own, keyed by [CacheKey], so that it is only
+/// read once per monitor.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CapabilitiesCache {
+    directory: PathBuf,
+}
+
+impl CapabilitiesCache {
+    /// A cache storing its files in `directory`, which is created when needed
+    pub fn new(directory: impl Into<PathBuf>) -> Self {
+        CapabilitiesCache {
+            directory: directory.into(),
+        }
+    }
+
+    /// A cache in the user cache directory: `~/Library/Caches/ddc-macos` on macOS, `$XDG_CACHE_HOME/ddc-macos` or
+    /// `~/.cache/ddc-macos` elsewhere. `None` if the home directory is unknown.
+    pub fn user_default() -> Option<Self> {
+        let home = std::env::var_os("HOME")
+            .filter(|home| !home.is_empty())
+            .map(PathBuf::from);
+        let base = if cfg!(target_os = "macos") {
+            home?.join("Library").join("Caches")
+        } else {
+            match std::env::var_os("XDG_CACHE_HOME").filter(|cache| !cache.is_empty()) {
+                Some(cache) => PathBuf::from(cache),
+                None => home?.join(".cache"),
+            }
+        };
+        Some(Self::new(base.join("ddc-macos")))
+    }
+
+    /// Directory holding the cache files
+    pub fn directory(&self) -> &Path {
+        &self.directory
+    }
+
+    /// Cached capabilities string of a monitor, `None` if it is not cached or cannot be read
+    pub fn load(&self, key: &CacheKey) -> Option<Vec<u8>> {
+        fs::read(self.path(key)).ok()
+    }
+
+    /// Caches the capabilities string of a monitor
+    pub fn store(&self, key: &CacheKey, capabilities: &[u8]) -> io::Result<()> {
+        fs::create_dir_all(&self.directory)?;
+        // Written under a temporary name first, so that readers never see a partial file
+        let path = self.path(key);
+        let temporary = path.with_extension(format!("tmp{}", std::process::id()));
+        fs::write(&temporary, capabilities)?;
+        fs::rename(&temporary, &path)
+    }
+
+    /// Removes the cached capabilities string of a monitor, if any
+    pub fn invalidate(&self, key: &CacheKey) -> io::Result<()> {
+        ignore_not_found(fs::remove_file(self.path(key)))
+    }
+
+    /// Removes all cached capabilities strings
+    pub fn clear(&self) -> io::Result<()> {
+        let entries = match fs::read_dir(&self.directory) {
+            Err(error) if error.kind() == io::ErrorKind::NotFound => return Ok(()),
+            entries => entries?,
+        };
+        for entry in entries {
+            let path = entry?.path();
+            if path.extension().is_some_and(|extension| extension == "caps") {
+                ignore_not_found(fs::remove_file(path))?;
+            }
+        }
+        Ok(())
+    }
+
+    fn path(&self, key: &CacheKey) -> PathBuf {
+        self.directory.join(key.file_name())
+    }
+}
+
+fn ignore_not_found(result: io::Result<()>) -> io::Result<()> {
+    match result {
+        Err(error) if error.kind() == io::ErrorKind::NotFound => Ok(()),
+        result => result,
+    }
+}
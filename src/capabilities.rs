@@ -0,0 +1,189 @@
+use std::collections::BTreeMap;
+use std::fmt;
+
+/// MCCS version a monitor claims to implement
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct MccsVersion {
+    /// Major version
+    pub major: u8,
+    /// Minor version
+    pub minor: u8,
+}
+
+impl fmt::Display for MccsVersion {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}.{}", self.major, self.minor)
+    }
+}
+
+/// A monitor capabilities string, as returned by `Ddc::capabilities_string`, decoded.
+///
+/// The string is a list of `key(value)` entries, for example `(prot(monitor)type(lcd)model(U2720Q)cmds(01 02 03)
+/// vcp(10 12 60(0F 11))mccs_ver(2.1))`. Parsing is lenient, as monitors commonly emit strings with missing outer
+/// or closing parentheses, lowercase or unseparated hex codes, trailing NUL bytes or stray text: whatever can be
+/// understood is kept, the rest is ignored.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Capabilities {
+    /// Protocol class, usually `monitor`
+    pub protocol: Option<String>,
+    /// Display type, e.g. `lcd`
+    pub display_type: Option<String>,
+    /// Model name
+    pub model: Option<String>,
+    /// MCCS version
+    pub mccs_version: Option<MccsVersion>,
+    /// Supported DDC/CI command opcodes
+    pub commands: Vec<u8>,
+    /// Supported VCP feature codes, with their allowed values for non-continuous features. The list of values is
+    /// empty for continuous features, and for non-continuous features when the monitor does not list them.
+    pub vcp_features: BTreeMap<u8, Vec<u8>>,
+    /// Names of manufacturer specific VCP features, from the `vcpname` entry
+    pub vcp_names: BTreeMap<u8, String>,
+    /// All other entries, such as `mswhql` or vendor specific ones, with their raw value
+    pub other: Vec<(String, String)>,
+}
+
+impl Capabilities {
+    /// Parses a capabilities string. This never fails, but may return empty capabilities.
+    pub fn parse(data: &[u8]) -> Self {
+        let string = String::from_utf8_lossy(data);
+        let mut capabilities = Capabilities::default();
+        for (key, value) in entries(&string) {
+            let text = || Some(value.trim().to_string()).filter(|text| !text.is_empty());
+            match key.to_ascii_lowercase().as_str() {
+                "prot" => capabilities.protocol = text(),
+                "type" => capabilities.display_type = text(),
+                "model" => capabilities.model = text(),
+                "mccs_ver" => capabilities.mccs_version = mccs_version(value),
+                "cmds" => capabilities
+                    .commands
+                    .extend(hex_codes(value).into_iter().map(|(code, _)| code)),
+                "vcp" => {
+                    for (code, values) in hex_codes(value) {
+                        let allowed = capabilities.vcp_features.entry(code).or_default();
+                        for (value, _) in hex_codes(values) {
+                            if !allowed.contains(&value) {
+                                allowed.push(value);
+                            }
+                        }
+                    }
+                }
+                "vcpname" => {
+                    for (code, name) in hex_codes(value) {
+                        capabilities.vcp_names.insert(code, name.trim().to_string());
+                    }
+                }
+                _ => capabilities.other.push((key.to_string(), value.to_string())),
+            }
+        }
+        capabilities
+    }
+
+    /// Whether the monitor lists the VCP feature
+    pub fn supports_vcp(&self, code: u8) -> bool {
+        self.vcp_features.contains_key(&code)
+    }
+
+    /// Allowed values of a VCP feature, `None` if the feature is not listed
+    pub fn vcp_values(&self, code: u8) -> Option<&[u8]> {
+        self.vcp_features.get(&code).map(Vec::as_slice)
+    }
+
+    /// Whether the monitor lists the DDC/CI command
+    pub fn supports_command(&self, opcode: u8) -> bool {
+        self.commands.contains(&opcode)
+    }
+
+    /// Raw value of an entry not decoded into a field, by key
+    pub fn other(&self, key: &str) -> Option<&str> {
+        self.other
+            .iter()
+            .find(|(name, _)| name.eq_ignore_ascii_case(key))
+            .map(|(_, value)| value.as_str())
+    }
+}
+
+/// Splits a string following an opening parenthesis into the content up to the matching closing parenthesis, and
+/// what follows it. Without a matching parenthesis, the content extends to the end of the string.
+fn group(string: &str) -> (&str, &str) {
+    let mut depth = 0usize;
+    for (index, c) in string.char_indices() {
+        match c {
+            '(' => depth += 1,
+            ')' if depth == 0 => return (&string[..index], &string[index + 1..]),
+            ')' => depth -= 1,
+            _ => {}
+        }
+    }
+    (string, "")
+}
+
+/// Splits a string into `key(value)` entries. The key is the last word before the opening parenthesis, so that
+/// stray text between entries is skipped. Entries of a parenthesized group without a key, such as the outer
+/// parentheses of the whole string, are included as if the group was not there.
+fn entries(mut string: &str) -> Vec<(&str, &str)> {
+    let mut entries = Vec::new();
+    while let Some(open) = string.find('(') {
+        let key = string[..open]
+            .rsplit(|c: char| c.is_whitespace() || c == ')')
+            .next()
+            .unwrap_or_default();
+        let (value, rest) = group(&string[open + 1..]);
+        if key.is_empty() {
+            entries.extend(self::entries(value));
+        } else {
+            entries.push((key, value));
+        }
+        string = rest;
+    }
+    entries
+}
+
+/// Parses a list of hex codes, each optionally followed by a parenthesized value. Codes may be separated by
+/// spaces or written back to back; anything else is skipped.
+fn hex_codes(string: &str) -> Vec<(u8, &str)> {
+    let bytes = string.as_bytes();
+    let mut codes = Vec::new();
+    let mut index = 0;
+    while index < bytes.len() {
+        if !bytes[index].is_ascii_hexdigit() {
+            if bytes[index] == b'(' {
+                // A value without a code before it
+                index += 1 + group(&string[index + 1..]).0.len() + 1;
+            } else {
+                index += 1;
+            }
+            continue;
+        }
+        let digits = if bytes.get(index + 1).is_some_and(u8::is_ascii_hexdigit) {
+            2
+        } else {
+            1
+        };
+        let code = u8::from_str_radix(&string[index..index + digits], 16).unwrap_or_default();
+        index += digits;
+        while bytes.get(index).is_some_and(u8::is_ascii_whitespace) {
+            index += 1;
+        }
+        let mut value = "";
+        if bytes.get(index) == Some(&b'(') {
+            value = group(&string[index + 1..]).0;
+            index += 1 + value.len() + 1;
+        }
+        codes.push((code, value));
+    }
+    codes
+}
+
+fn mccs_version(value: &str) -> Option<MccsVersion> {
+    let mut numbers = value
+        .trim()
+        .split('.')
+        .map(|number| number.trim_matches(|c: char| !c.is_ascii_digit()).parse::<u8>());
+    Some(MccsVersion {
+        major: numbers.next()?.ok()?,
+        minor: numbers.next().unwrap_or(Ok(0)).ok()?,
+    })
+}
@@ -0,0 +1,137 @@
+//! Framing of DDC/CI packets, independent of the platform and of the transport.
+//!
+//! Commands are sent to the display as `sub-address, 0x80 | length, data…, checksum`, and replies come back as
+//! `source address, 0x80 | length, data…, checksum`. Checksums are the XOR of all bytes, including the I2C
+//! addresses that are not part of the packets themselves.
+
+use crate::error::Error;
+use ddc::{ErrorCode, SUB_ADDRESS_DDC_CI};
+use std::iter;
+
+/// Maximum length of the data carried by a single DDC/CI packet
+pub const MAX_DATA_LEN: usize = 36;
+
+/// Maximum length of the table or capabilities string data carried by a single fragment
+pub const MAX_FRAGMENT_LEN: usize = 32;
+
+/// Number of bytes a packet adds around its data: sub-address or source address, length and checksum
+pub const PACKET_OVERHEAD: usize = 3;
+
+/// Returns the opcode of the reply expected for a request, if the request has a reply
+pub fn reply_opcode(request: &[u8]) -> Option<u8> {
+    match request.first()? {
+        // Get VCP Feature
+        0x01 => Some(0x02),
+        // Get Timing Report
+        0x07 => Some(0x4e),
+        // Table Read
+        0xe2 => Some(0xe4),
+        // Capabilities Request
+        0xf3 => Some(0xe3),
+        _ => None,
+    }
+}
+
+/// Computes a DDC/CI checksum: the XOR of all bytes
+pub fn checksum<I: IntoIterator<Item = u8>>(iter: I) -> u8 {
+    iter.into_iter().fold(0u8, |sum, v| sum ^ v)
+}
+
+/// Encodes DDC/CI commands for the device at a given I2C address
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Encoder {
+    i2c_address: u16,
+}
+
+impl Encoder {
+    /// Create an encoder for the device at `i2c_address`
+    pub fn new(i2c_address: u16) -> Self {
+        Encoder { i2c_address }
+    }
+
+    /// I2C address of the device this encoder writes to
+    pub fn i2c_address(&self) -> u16 {
+        self.i2c_address
+    }
+
+    /// Encodes a DDC/CI command into `packet`, returning the part of it that must be sent.
+    ///
+    /// `packet.len()` must be at least [PACKET_OVERHEAD] bytes larger than `data.len()`, and `data` cannot be
+    /// longer than [MAX_DATA_LEN].
+    pub fn encode<'a>(&self, data: &[u8], packet: &'a mut [u8]) -> &'a [u8] {
+        assert!(data.len() <= MAX_DATA_LEN);
+        packet[0] = SUB_ADDRESS_DDC_CI;
+        packet[1] = 0x80 | data.len() as u8;
+        packet[2..2 + data.len()].copy_from_slice(data);
+        packet[2 + data.len()] =
+            checksum(iter::once((self.i2c_address as u8) << 1).chain(packet[..2 + data.len()].iter().cloned()));
+        &packet[..PACKET_OVERHEAD + data.len()]
+    }
+}
+
+/// Decodes DDC/CI replies from the device at a given I2C address
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Decoder {
+    i2c_address: u16,
+}
+
+impl Decoder {
+    /// Create a decoder for replies of the device at `i2c_address`
+    pub fn new(i2c_address: u16) -> Self {
+        Decoder { i2c_address }
+    }
+
+    /// I2C address of the device this decoder reads from
+    pub fn i2c_address(&self) -> u16 {
+        self.i2c_address
+    }
+
+    /// Validates the length, checksum and source address of a reply, returning its data without the packet headers.
+    ///
+    /// An empty response, when no reply was read, decodes to empty data. A DDC/CI null message, which displays
+    /// send when they are busy or have nothing to reply, is reported as [Error::MonitorBusy].
+    pub fn decode<'a>(&self, response: &'a mut [u8]) -> Result<&'a mut [u8], Error> {
+        if response.is_empty() {
+            return Ok(response);
+        };
+        if response.len() < 2 {
+            return Err(Error::Ddc(ErrorCode::InvalidLength));
+        }
+        let len = (response[1] & 0x7f) as usize;
+        if len + 2 >= response.len() {
+            return Err(Error::Ddc(ErrorCode::InvalidLength));
+        }
+        let checksum = checksum(
+            iter::once(((self.i2c_address << 1) | 1) as u8)
+                .chain(iter::once(SUB_ADDRESS_DDC_CI))
+                .chain(response[1..2 + len].iter().cloned()),
+        );
+        if response[2 + len] != checksum {
+            return Err(Error::Ddc(ErrorCode::InvalidChecksum));
+        }
+        let source_address = (self.i2c_address << 1) as u8;
+        if response[0] != source_address {
+            return Err(Error::SourceAddressMismatch {
+                expected: source_address,
+                actual: response[0],
+            });
+        }
+        if len == 0 {
+            return Err(Error::MonitorBusy);
+        }
+        Ok(&mut response[2..2 + len])
+    }
+
+    /// Decodes a reply like [Decoder::decode], and also checks that its opcode answers `request`, the command data
+    /// that was sent.
+    pub fn decode_reply<'a>(&self, request: &[u8], response: &'a mut [u8]) -> Result<&'a mut [u8], Error> {
+        let data = self.decode(response)?;
+        match reply_opcode(request) {
+            Some(expected) if !data.is_empty() && data[0] != expected => Err(Error::OpcodeMismatch {
+                expected,
+                actual: data[0],
+            }),
+            _ => Ok(data),
+        }
+    }
+}
@@ -0,0 +1,514 @@
+use crate::edid::{DetailedTiming, BLOCK_LEN};
+
+/// Extension block tag of CTA-861 extensions
+pub const CTA_EXTENSION_TAG: u8 = 0x02;
+
+/// IEEE OUI of the HDMI Licensing vendor-specific data block
+pub const HDMI_OUI: u32 = 0x000c03;
+
+/// IEEE OUI of the HDMI Forum vendor-specific data block
+pub const HDMI_FORUM_OUI: u32 = 0xc45dd8;
+
+/// A parsed CTA-861 extension block
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct CtaExtension {
+    /// Revision of the extension
+    pub revision: u8,
+    /// The display underscans IT formats by default
+    pub underscan: bool,
+    /// Basic audio is supported
+    pub basic_audio: bool,
+    /// YCbCr 4:4:4 is supported
+    pub ycbcr444: bool,
+    /// YCbCr 4:2:2 is supported
+    pub ycbcr422: bool,
+    /// Number of native detailed timings
+    pub native_detailed_timings: u8,
+    /// Data blocks, in order
+    pub data_blocks: Vec<DataBlock>,
+    /// Detailed timings following the data blocks
+    pub detailed_timings: Vec<DetailedTiming>,
+}
+
+/// A CTA-861 data block
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum DataBlock {
+    /// Short audio descriptors
+    Audio(Vec<ShortAudioDescriptor>),
+    /// Short video descriptors
+    Video(Vec<ShortVideoDescriptor>),
+    /// HDMI Licensing vendor-specific data block
+    Hdmi(HdmiVsdb),
+    /// HDMI Forum vendor-specific data block
+    HdmiForum(HdmiForumVsdb),
+    /// Any other vendor-specific data block, with its OUI and payload after the OUI
+    VendorSpecific(u32, Vec<u8>),
+    /// Speaker allocation
+    SpeakerAllocation(SpeakerAllocation),
+    /// Colorimetry
+    Colorimetry(Colorimetry),
+    /// HDR static metadata
+    HdrStaticMetadata(HdrStaticMetadata),
+    /// Video formats that are only supported with YCbCr 4:2:0 sampling
+    Ycbcr420Video(Vec<ShortVideoDescriptor>),
+    /// Bitmap of the video data block formats that also support YCbCr 4:2:0 sampling. An empty map means all of
+    /// them do.
+    Ycbcr420CapabilityMap(Vec<u8>),
+    /// Any other data block, with its tag, extended tag for tag 7, and payload
+    Other(u8, Option<u8>, Vec<u8>),
+}
+
+/// Audio coding type of a short audio descriptor
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum AudioFormat {
+    /// Linear PCM
+    Lpcm,
+    /// AC-3
+    Ac3,
+    /// MPEG-1 (layers 1 and 2)
+    Mpeg1,
+    /// MP3
+    Mp3,
+    /// MPEG-2 multichannel
+    Mpeg2,
+    /// AAC LC
+    AacLc,
+    /// DTS
+    Dts,
+    /// ATRAC
+    Atrac,
+    /// One Bit Audio
+    OneBitAudio,
+    /// Enhanced AC-3
+    EnhancedAc3,
+    /// DTS-HD
+    DtsHd,
+    /// MAT (MLP)
+    Mat,
+    /// DST
+    Dst,
+    /// WMA Pro
+    WmaPro,
+    /// Extended or reserved coding type code
+    Other(u8),
+}
+
+/// A short audio descriptor
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ShortAudioDescriptor {
+    /// Audio coding type
+    pub format: AudioFormat,
+    /// Maximum number of channels
+    pub channels: u8,
+    /// Supported sample rates in Hz
+    pub sample_rates: Vec<u32>,
+    /// Supported bit depths, for LPCM
+    pub bit_depths: Vec<u8>,
+    /// Maximum bit rate in kbit/s, for AC-3, MPEG, DTS and ATRAC
+    pub max_bit_rate_kbps: Option<u32>,
+}
+
+/// A short video descriptor
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ShortVideoDescriptor {
+    /// Video identification code
+    pub vic: u8,
+    /// The format is a native format of the display
+    pub native: bool,
+}
+
+/// HDMI Licensing vendor-specific data block
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct HdmiVsdb {
+    /// CEC physical address, `A.B.C.D` nibbles
+    pub physical_address: u16,
+    /// Supports_AI flag
+    pub supports_ai: bool,
+    /// 48 bits per pixel deep color
+    pub deep_color_48: bool,
+    /// 36 bits per pixel deep color
+    pub deep_color_36: bool,
+    /// 30 bits per pixel deep color
+    pub deep_color_30: bool,
+    /// Deep color in YCbCr 4:4:4
+    pub deep_color_ycbcr444: bool,
+    /// Dual-link DVI
+    pub dvi_dual: bool,
+    /// Maximum TMDS clock in MHz, if reported
+    pub max_tmds_clock_mhz: Option<u16>,
+}
+
+/// HDMI Forum vendor-specific data block
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct HdmiForumVsdb {
+    /// Version of the block
+    pub version: u8,
+    /// Maximum TMDS character rate in MHz, if above 340 MHz
+    pub max_tmds_character_rate_mhz: Option<u16>,
+    /// SCDC is present
+    pub scdc_present: bool,
+    /// SCDC read requests are supported
+    pub read_request_capable: bool,
+    /// Scrambling is supported at 340 Mcsc and below
+    pub lte_340mcsc_scramble: bool,
+    /// Maximum fixed rate link code, 0 when FRL is not supported
+    pub max_frl_rate: u8,
+    /// 48 bits per pixel deep color in YCbCr 4:2:0
+    pub deep_color_420_48: bool,
+    /// 36 bits per pixel deep color in YCbCr 4:2:0
+    pub deep_color_420_36: bool,
+    /// 30 bits per pixel deep color in YCbCr 4:2:0
+    pub deep_color_420_30: bool,
+}
+
+/// Speaker allocation: which speakers are present
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct SpeakerAllocation(pub u32);
+
+/// Speaker names, in bit order of the speaker allocation data block
+const SPEAKERS: [&str; 21] = [
+    "FL/FR",
+    "LFE1",
+    "FC",
+    "BL/BR",
+    "BC",
+    "FLc/FRc",
+    "RLC/RRC",
+    "FLw/FRw",
+    "TpFL/TpFR",
+    "TpC",
+    "TpFC",
+    "LS/RS",
+    "LFE2",
+    "TpBC",
+    "SiL/SiR",
+    "TpSiL/TpSiR",
+    "TpBL/TpBR",
+    "BtFC",
+    "BtFL/BtFR",
+    "TpLS/TpRS",
+    "LSd/RSd",
+];
+
+impl SpeakerAllocation {
+    /// Names of the speakers that are present, e.g. `FL/FR` for front left and right
+    pub fn speakers(&self) -> Vec<&'static str> {
+        SPEAKERS
+            .iter()
+            .enumerate()
+            .filter(|(bit, _)| self.0 & (1 << bit) != 0)
+            .map(|(_, name)| *name)
+            .collect()
+    }
+}
+
+/// Colorimetry data block: supported extended color spaces
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Colorimetry {
+    /// xvYCC 601
+    pub xvycc601: bool,
+    /// xvYCC 709
+    pub xvycc709: bool,
+    /// sYCC 601
+    pub sycc601: bool,
+    /// opYCC 601
+    pub opycc601: bool,
+    /// opRGB
+    pub oprgb: bool,
+    /// BT.2020 constant luminance YCbCr
+    pub bt2020_cycc: bool,
+    /// BT.2020 YCbCr
+    pub bt2020_ycc: bool,
+    /// BT.2020 RGB
+    pub bt2020_rgb: bool,
+    /// DCI-P3
+    pub dci_p3: bool,
+}
+
+/// HDR static metadata data block
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct HdrStaticMetadata {
+    /// Traditional gamma, SDR luminance range
+    pub sdr: bool,
+    /// Traditional gamma, HDR luminance range
+    pub traditional_hdr: bool,
+    /// SMPTE ST 2084 (PQ)
+    pub pq: bool,
+    /// Hybrid Log-Gamma
+    pub hlg: bool,
+    /// Static metadata type 1 is supported
+    pub static_metadata_type1: bool,
+    /// Desired content maximum luminance in cd/m²
+    pub max_luminance: Option<f32>,
+    /// Desired content maximum frame-average luminance in cd/m²
+    pub max_frame_average_luminance: Option<f32>,
+    /// Desired content minimum luminance in cd/m²
+    pub min_luminance: Option<f32>,
+}
+
+impl CtaExtension {
+    /// Parses a CTA-861 extension block, returning `None` if the block is not one
+    pub fn parse(block: &[u8]) -> Option<Self> {
+        if block.len() < BLOCK_LEN || block[0] != CTA_EXTENSION_TAG {
+            return None;
+        }
+        let dtd_offset = (block[2] as usize).min(BLOCK_LEN - 1);
+        let data_blocks = if block[1] >= 3 && dtd_offset >= 4 {
+            parse_data_blocks(&block[4..dtd_offset])
+        } else {
+            Vec::new()
+        };
+        let detailed_timings = if dtd_offset >= 4 {
+            block[dtd_offset..BLOCK_LEN - 1]
+                .chunks_exact(18)
+                .map_while(DetailedTiming::parse)
+                .collect()
+        } else {
+            Vec::new()
+        };
+        Some(CtaExtension {
+            revision: block[1],
+            underscan: block[3] & 0x80 != 0,
+            basic_audio: block[3] & 0x40 != 0,
+            ycbcr444: block[3] & 0x20 != 0,
+            ycbcr422: block[3] & 0x10 != 0,
+            native_detailed_timings: block[3] & 0x0f,
+            data_blocks,
+            detailed_timings,
+        })
+    }
+
+    /// Short audio descriptors of all audio data blocks
+    pub fn audio_descriptors(&self) -> impl Iterator<Item = &ShortAudioDescriptor> {
+        self.data_blocks.iter().flat_map(|block| match block {
+            DataBlock::Audio(descriptors) => descriptors.as_slice(),
+            _ => &[],
+        })
+    }
+
+    /// Short video descriptors of all video data blocks
+    pub fn video_descriptors(&self) -> impl Iterator<Item = &ShortVideoDescriptor> {
+        self.data_blocks.iter().flat_map(|block| match block {
+            DataBlock::Video(descriptors) => descriptors.as_slice(),
+            _ => &[],
+        })
+    }
+
+    /// HDR static metadata, if the display supports HDR
+    pub fn hdr_static_metadata(&self) -> Option<&HdrStaticMetadata> {
+        self.data_blocks.iter().find_map(|block| match block {
+            DataBlock::HdrStaticMetadata(metadata) => Some(metadata),
+            _ => None,
+        })
+    }
+
+    /// Colorimetry data block, if present
+    pub fn colorimetry(&self) -> Option<&Colorimetry> {
+        self.data_blocks.iter().find_map(|block| match block {
+            DataBlock::Colorimetry(colorimetry) => Some(colorimetry),
+            _ => None,
+        })
+    }
+
+    /// Speaker allocation, if present
+    pub fn speaker_allocation(&self) -> Option<&SpeakerAllocation> {
+        self.data_blocks.iter().find_map(|block| match block {
+            DataBlock::SpeakerAllocation(allocation) => Some(allocation),
+            _ => None,
+        })
+    }
+
+    /// Video identification codes of the formats supported with YCbCr 4:2:0 sampling, either exclusively or
+    /// in addition to RGB and other samplings
+    pub fn ycbcr420_vics(&self) -> Vec<u8> {
+        let video: Vec<u8> = self.video_descriptors().map(|svd| svd.vic).collect();
+        let mut vics = Vec::new();
+        for block in &self.data_blocks {
+            match block {
+                DataBlock::Ycbcr420Video(descriptors) => vics.extend(descriptors.iter().map(|svd| svd.vic)),
+                DataBlock::Ycbcr420CapabilityMap(map) if map.is_empty() => vics.extend(&video),
+                DataBlock::Ycbcr420CapabilityMap(map) => vics.extend(
+                    video
+                        .iter()
+                        .enumerate()
+                        .filter(|(index, _)| map.get(index / 8).is_some_and(|bits| bits & (1 << (index % 8)) != 0))
+                        .map(|(_, vic)| *vic),
+                ),
+                _ => (),
+            }
+        }
+        vics
+    }
+}
+
+fn parse_data_blocks(mut data: &[u8]) -> Vec<DataBlock> {
+    let mut blocks = Vec::new();
+    while let Some(header) = data.first() {
+        let len = (header & 0x1f) as usize;
+        if data.len() < len + 1 {
+            break;
+        }
+        blocks.push(parse_data_block(header >> 5, &data[1..1 + len]));
+        data = &data[1 + len..];
+    }
+    blocks
+}
+
+fn parse_data_block(tag: u8, payload: &[u8]) -> DataBlock {
+    match tag {
+        1 => DataBlock::Audio(payload.chunks_exact(3).map(short_audio_descriptor).collect()),
+        2 => DataBlock::Video(payload.iter().map(|svd| short_video_descriptor(*svd)).collect()),
+        3 if payload.len() >= 3 => {
+            let oui = u32::from_le_bytes([payload[0], payload[1], payload[2], 0]);
+            match oui {
+                HDMI_OUI if payload.len() >= 5 => DataBlock::Hdmi(hdmi_vsdb(payload)),
+                HDMI_FORUM_OUI if payload.len() >= 7 => DataBlock::HdmiForum(hdmi_forum_vsdb(payload)),
+                _ => DataBlock::VendorSpecific(oui, payload[3..].to_vec()),
+            }
+        }
+        4 if payload.len() >= 3 => DataBlock::SpeakerAllocation(SpeakerAllocation(u32::from_le_bytes([
+            payload[0], payload[1], payload[2], 0,
+        ]))),
+        7 if !payload.is_empty() => match (payload[0], &payload[1..]) {
+            (0x05, data) if !data.is_empty() => DataBlock::Colorimetry(colorimetry(data)),
+            (0x06, data) if data.len() >= 2 => DataBlock::HdrStaticMetadata(hdr_static_metadata(data)),
+            (0x0e, data) => DataBlock::Ycbcr420Video(data.iter().map(|svd| short_video_descriptor(*svd)).collect()),
+            (0x0f, data) => DataBlock::Ycbcr420CapabilityMap(data.to_vec()),
+            (extended_tag, data) => DataBlock::Other(tag, Some(extended_tag), data.to_vec()),
+        },
+        _ => DataBlock::Other(tag, None, payload.to_vec()),
+    }
+}
+
+fn short_audio_descriptor(sad: &[u8]) -> ShortAudioDescriptor {
+    let code = (sad[0] >> 3) & 0x0f;
+    let format = match code {
+        1 => AudioFormat::Lpcm,
+        2 => AudioFormat::Ac3,
+        3 => AudioFormat::Mpeg1,
+        4 => AudioFormat::Mp3,
+        5 => AudioFormat::Mpeg2,
+        6 => AudioFormat::AacLc,
+        7 => AudioFormat::Dts,
+        8 => AudioFormat::Atrac,
+        9 => AudioFormat::OneBitAudio,
+        10 => AudioFormat::EnhancedAc3,
+        11 => AudioFormat::DtsHd,
+        12 => AudioFormat::Mat,
+        13 => AudioFormat::Dst,
+        14 => AudioFormat::WmaPro,
+        other => AudioFormat::Other(other),
+    };
+    const SAMPLE_RATES: [u32; 7] = [32_000, 44_100, 48_000, 88_200, 96_000, 176_400, 192_000];
+    ShortAudioDescriptor {
+        format,
+        channels: (sad[0] & 0x07) + 1,
+        sample_rates: SAMPLE_RATES
+            .iter()
+            .enumerate()
+            .filter(|(bit, _)| sad[1] & (1 << bit) != 0)
+            .map(|(_, rate)| *rate)
+            .collect(),
+        bit_depths: if format == AudioFormat::Lpcm {
+            [16, 20, 24]
+                .iter()
+                .enumerate()
+                .filter(|(bit, _)| sad[2] & (1 << bit) != 0)
+                .map(|(_, depth)| *depth)
+                .collect()
+        } else {
+            Vec::new()
+        },
+        max_bit_rate_kbps: match code {
+            2..=8 => Some(sad[2] as u32 * 8),
+            _ => None,
+        },
+    }
+}
+
+fn short_video_descriptor(svd: u8) -> ShortVideoDescriptor {
+    match svd {
+        // VICs 1 to 64 use bit 7 as the native flag
+        129..=192 => ShortVideoDescriptor {
+            vic: svd & 0x7f,
+            native: true,
+        },
+        vic => ShortVideoDescriptor { vic, native: false },
+    }
+}
+
+fn hdmi_vsdb(payload: &[u8]) -> HdmiVsdb {
+    let flags = payload.get(5).copied().unwrap_or_default();
+    HdmiVsdb {
+        physical_address: u16::from_be_bytes([payload[3], payload[4]]),
+        supports_ai: flags & 0x80 != 0,
+        deep_color_48: flags & 0x40 != 0,
+        deep_color_36: flags & 0x20 != 0,
+        deep_color_30: flags & 0x10 != 0,
+        deep_color_ycbcr444: flags & 0x08 != 0,
+        dvi_dual: flags & 0x01 != 0,
+        max_tmds_clock_mhz: payload
+            .get(6)
+            .filter(|clock| **clock != 0)
+            .map(|clock| *clock as u16 * 5),
+    }
+}
+
+fn hdmi_forum_vsdb(payload: &[u8]) -> HdmiForumVsdb {
+    HdmiForumVsdb {
+        version: payload[3],
+        max_tmds_character_rate_mhz: match payload[4] {
+            0 => None,
+            rate => Some(rate as u16 * 5),
+        },
+        scdc_present: payload[5] & 0x80 != 0,
+        read_request_capable: payload[5] & 0x40 != 0,
+        lte_340mcsc_scramble: payload[5] & 0x08 != 0,
+        max_frl_rate: payload[6] >> 4,
+        deep_color_420_48: payload[6] & 0x04 != 0,
+        deep_color_420_36: payload[6] & 0x02 != 0,
+        deep_color_420_30: payload[6] & 0x01 != 0,
+    }
+}
+
+fn colorimetry(data: &[u8]) -> Colorimetry {
+    let flags = data[0];
+    Colorimetry {
+        xvycc601: flags & 0x01 != 0,
+        xvycc709: flags & 0x02 != 0,
+        sycc601: flags & 0x04 != 0,
+        opycc601: flags & 0x08 != 0,
+        oprgb: flags & 0x10 != 0,
+        bt2020_cycc: flags & 0x20 != 0,
+        bt2020_ycc: flags & 0x40 != 0,
+        bt2020_rgb: flags & 0x80 != 0,
+        dci_p3: data.get(1).is_some_and(|flags| flags & 0x80 != 0),
+    }
+}
+
+fn hdr_static_metadata(data: &[u8]) -> HdrStaticMetadata {
+    let max_luminance = data.get(2).map(|code| 50.0 * 2f32.powf(*code as f32 / 32.0));
+    HdrStaticMetadata {
+        sdr: data[0] & 0x01 != 0,
+        traditional_hdr: data[0] & 0x02 != 0,
+        pq: data[0] & 0x04 != 0,
+        hlg: data[0] & 0x08 != 0,
+        static_metadata_type1: data[1] & 0x01 != 0,
+        max_luminance,
+        max_frame_average_luminance: data.get(3).map(|code| 50.0 * 2f32.powf(*code as f32 / 32.0)),
+        min_luminance: match (max_luminance, data.get(4)) {
+            (Some(max), Some(code)) => Some(max * (*code as f32 / 255.0).powi(2) / 100.0),
+            _ => None,
+        },
+    }
+}
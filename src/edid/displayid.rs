@@ -0,0 +1,360 @@
+use crate::edid::BLOCK_LEN;
+
+/// Extension block tag of DisplayID extensions
+pub const DISPLAYID_EXTENSION_TAG: u8 = 0x70;
+
+/// A DisplayID section carried in an EDID extension block
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct DisplayIdExtension {
+    /// DisplayID version: 1 or 2
+    pub version: u8,
+    /// DisplayID revision
+    pub revision: u8,
+    /// Product type (DisplayID 1.x) or primary use case (DisplayID 2.0)
+    pub product_type: u8,
+    /// Data blocks, in order
+    pub data_blocks: Vec<DisplayIdBlock>,
+}
+
+/// A DisplayID data block
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum DisplayIdBlock {
+    /// Product identification
+    ProductIdentification(ProductIdentification),
+    /// Display parameters
+    DisplayParameters(DisplayParameters),
+    /// Type I (DisplayID 1.x) or type VII (DisplayID 2.0) detailed timings
+    DetailedTimings(Vec<DisplayIdTiming>),
+    /// Tiled display topology
+    TiledDisplayTopology(TiledDisplayTopology),
+    /// Any other data block, with its tag, revision and payload
+    Other(u8, u8, Vec<u8>),
+}
+
+/// Product identification data block
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ProductIdentification {
+    /// PNP ID (DisplayID 1.x) or IEEE OUI in hex (DisplayID 2.0) of the manufacturer
+    pub manufacturer: String,
+    /// Product code
+    pub product_code: u16,
+    /// Serial number, zero if not used
+    pub serial_number: u32,
+    /// Week of manufacture, zero if not specified
+    pub week: u8,
+    /// Year of manufacture
+    pub year: u16,
+    /// Product name
+    pub product_name: String,
+}
+
+/// Display parameters data block
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct DisplayParameters {
+    /// Horizontal image size in millimeters
+    pub horizontal_image_size_mm: f32,
+    /// Vertical image size in millimeters
+    pub vertical_image_size_mm: f32,
+    /// Native horizontal pixel count
+    pub horizontal_pixels: u16,
+    /// Native vertical pixel count
+    pub vertical_pixels: u16,
+    /// Native gamma, if specified
+    pub gamma: Option<f32>,
+    /// Maximum luminance with full coverage in cd/m², DisplayID 2.0 only
+    pub max_luminance: Option<f32>,
+    /// Maximum luminance with 10% coverage in cd/m², DisplayID 2.0 only
+    pub max_luminance_10_percent: Option<f32>,
+    /// Minimum luminance in cd/m², DisplayID 2.0 only
+    pub min_luminance: Option<f32>,
+}
+
+/// A type I or type VII detailed timing. Sizes are stored minus one on two bytes, so they range from 1 to 65536.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct DisplayIdTiming {
+    /// Pixel clock in kHz
+    pub pixel_clock_khz: u32,
+    /// Preferred timing
+    pub preferred: bool,
+    /// Interlaced video mode
+    pub interlaced: bool,
+    /// Aspect ratio code
+    pub aspect_ratio: u8,
+    /// Horizontal addressable pixels
+    pub horizontal_active: u32,
+    /// Horizontal blanking pixels
+    pub horizontal_blanking: u32,
+    /// Horizontal front porch in pixels
+    pub horizontal_front_porch: u32,
+    /// Horizontal sync pulse width in pixels
+    pub horizontal_sync_width: u32,
+    /// Horizontal sync pulse is positive
+    pub horizontal_sync_positive: bool,
+    /// Vertical addressable lines
+    pub vertical_active: u32,
+    /// Vertical blanking lines
+    pub vertical_blanking: u32,
+    /// Vertical front porch in lines
+    pub vertical_front_porch: u32,
+    /// Vertical sync pulse width in lines
+    pub vertical_sync_width: u32,
+    /// Vertical sync pulse is positive
+    pub vertical_sync_positive: bool,
+}
+
+impl DisplayIdTiming {
+    /// Vertical refresh rate in Hz
+    pub fn refresh_rate(&self) -> f64 {
+        let total = (self.horizontal_active as u64 + self.horizontal_blanking as u64)
+            * (self.vertical_active as u64 + self.vertical_blanking as u64);
+        if total == 0 {
+            return 0.0;
+        }
+        self.pixel_clock_khz as f64 * 1000.0 / total as f64
+    }
+}
+
+/// Tiled display topology: how a display made of several tiles is laid out
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct TiledDisplayTopology {
+    /// All tiles are in a single physical enclosure
+    pub single_enclosure: bool,
+    /// Number of tiles horizontally
+    pub horizontal_tiles: u8,
+    /// Number of tiles vertically
+    pub vertical_tiles: u8,
+    /// Horizontal position of this tile, starting at 0 on the left
+    pub horizontal_location: u8,
+    /// Vertical position of this tile, starting at 0 at the top
+    pub vertical_location: u8,
+    /// Width of a tile in pixels
+    pub tile_width: u32,
+    /// Height of a tile in pixels
+    pub tile_height: u32,
+    /// PNP ID or OUI of the manufacturer of the tiled display
+    pub manufacturer: String,
+    /// Product code of the tiled display
+    pub product_code: u16,
+    /// Serial number of the tiled display, shared by all its tiles
+    pub serial_number: u32,
+}
+
+impl TiledDisplayTopology {
+    /// Resolution of the whole tiled display, assuming tiles of equal size
+    pub fn total_resolution(&self) -> (u32, u32) {
+        (
+            self.tile_width * self.horizontal_tiles as u32,
+            self.tile_height * self.vertical_tiles as u32,
+        )
+    }
+}
+
+impl DisplayIdExtension {
+    /// Parses a DisplayID extension block, returning `None` if the block is not one
+    pub fn parse(block: &[u8]) -> Option<Self> {
+        if block.len() < BLOCK_LEN || block[0] != DISPLAYID_EXTENSION_TAG {
+            return None;
+        }
+        let version = block[1] >> 4;
+        let end = (5 + block[2] as usize).min(BLOCK_LEN - 1);
+        let mut data = &block[5..end];
+        let mut data_blocks = Vec::new();
+        // The section is padded with zeros, and tag 0 is a valid DisplayID 1.x block
+        while data.len() >= 3 && data.iter().any(|&byte| byte != 0) {
+            let len = data[2] as usize;
+            if data.len() < 3 + len {
+                break;
+            }
+            data_blocks.push(parse_data_block(version, data[0], data[1], &data[3..3 + len]));
+            data = &data[3 + len..];
+        }
+        Some(DisplayIdExtension {
+            version,
+            revision: block[1] & 0x0f,
+            product_type: block[3],
+            data_blocks,
+        })
+    }
+
+    /// All detailed timings of the section
+    pub fn detailed_timings(&self) -> impl Iterator<Item = &DisplayIdTiming> {
+        self.data_blocks.iter().flat_map(|block| match block {
+            DisplayIdBlock::DetailedTimings(timings) => timings.as_slice(),
+            _ => &[],
+        })
+    }
+
+    /// Display parameters, if the section has them
+    pub fn display_parameters(&self) -> Option<&DisplayParameters> {
+        self.data_blocks.iter().find_map(|block| match block {
+            DisplayIdBlock::DisplayParameters(parameters) => Some(parameters),
+            _ => None,
+        })
+    }
+
+    /// Product identification, if the section has it
+    pub fn product_identification(&self) -> Option<&ProductIdentification> {
+        self.data_blocks.iter().find_map(|block| match block {
+            DisplayIdBlock::ProductIdentification(product) => Some(product),
+            _ => None,
+        })
+    }
+
+    /// Tiled display topology, if this display is one tile of a larger display
+    pub fn tiled_display_topology(&self) -> Option<&TiledDisplayTopology> {
+        self.data_blocks.iter().find_map(|block| match block {
+            DisplayIdBlock::TiledDisplayTopology(topology) => Some(topology),
+            _ => None,
+        })
+    }
+
+    /// Native resolution: the pixel counts of the display parameters, or else the preferred detailed timing
+    pub fn native_resolution(&self) -> Option<(u32, u32)> {
+        if let Some(parameters) = self.display_parameters() {
+            if parameters.horizontal_pixels != 0 && parameters.vertical_pixels != 0 {
+                return Some((parameters.horizontal_pixels as u32, parameters.vertical_pixels as u32));
+            }
+        }
+        self.detailed_timings()
+            .find(|timing| timing.preferred)
+            .or_else(|| self.detailed_timings().next())
+            .map(|timing| (timing.horizontal_active, timing.vertical_active))
+    }
+}
+
+fn parse_data_block(version: u8, tag: u8, revision: u8, payload: &[u8]) -> DisplayIdBlock {
+    match (version, tag) {
+        (1, 0x00) | (2, 0x20) if payload.len() >= 12 => {
+            DisplayIdBlock::ProductIdentification(product_identification(version, payload))
+        }
+        (1, 0x01) if payload.len() >= 12 => DisplayIdBlock::DisplayParameters(display_parameters_v1(payload)),
+        (2, 0x21) if payload.len() >= 29 => DisplayIdBlock::DisplayParameters(display_parameters_v2(revision, payload)),
+        // Type I timings have a pixel clock in 10 kHz units, type VII in 1 kHz units
+        (1, 0x03) => DisplayIdBlock::DetailedTimings(payload.chunks_exact(20).map(|t| timing(t, 10)).collect()),
+        (2, 0x22) => DisplayIdBlock::DetailedTimings(payload.chunks_exact(20).map(|t| timing(t, 1)).collect()),
+        (1, 0x12) | (2, 0x28) if payload.len() >= 22 => {
+            DisplayIdBlock::TiledDisplayTopology(tiled_display_topology(version, payload))
+        }
+        _ => DisplayIdBlock::Other(tag, revision, payload.to_vec()),
+    }
+}
+
+fn manufacturer(version: u8, id: &[u8]) -> String {
+    if version >= 2 {
+        format!("{:02X}{:02X}{:02X}", id[0], id[1], id[2])
+    } else {
+        String::from_utf8_lossy(id).into_owned()
+    }
+}
+
+fn product_identification(version: u8, payload: &[u8]) -> ProductIdentification {
+    let name_len = payload[11] as usize;
+    let name = payload.get(12..12 + name_len).unwrap_or(&payload[12..]);
+    ProductIdentification {
+        manufacturer: manufacturer(version, &payload[0..3]),
+        product_code: u16::from_le_bytes([payload[3], payload[4]]),
+        serial_number: u32::from_le_bytes([payload[5], payload[6], payload[7], payload[8]]),
+        week: payload[9],
+        year: payload[10] as u16 + 2000,
+        product_name: String::from_utf8_lossy(name).trim_end().to_string(),
+    }
+}
+
+fn le16(data: &[u8], index: usize) -> u16 {
+    u16::from_le_bytes([data[index], data[index + 1]])
+}
+
+fn gamma(value: u8) -> Option<f32> {
+    match value {
+        0xff => None,
+        gamma => Some((gamma as f32 + 100.0) / 100.0),
+    }
+}
+
+fn display_parameters_v1(payload: &[u8]) -> DisplayParameters {
+    DisplayParameters {
+        horizontal_image_size_mm: le16(payload, 0) as f32 / 10.0,
+        vertical_image_size_mm: le16(payload, 2) as f32 / 10.0,
+        horizontal_pixels: le16(payload, 4),
+        vertical_pixels: le16(payload, 6),
+        gamma: gamma(payload[9]),
+        max_luminance: None,
+        max_luminance_10_percent: None,
+        min_luminance: None,
+    }
+}
+
+fn display_parameters_v2(revision: u8, payload: &[u8]) -> DisplayParameters {
+    // Bit 7 of the block revision selects 1 mm instead of 0.1 mm image size units
+    let unit = if revision & 0x80 != 0 { 1.0 } else { 0.1 };
+    let luminance = |index| match le16(payload, index) {
+        0 => None,
+        value => Some(half_float(value)),
+    };
+    DisplayParameters {
+        horizontal_image_size_mm: le16(payload, 0) as f32 * unit,
+        vertical_image_size_mm: le16(payload, 2) as f32 * unit,
+        horizontal_pixels: le16(payload, 4),
+        vertical_pixels: le16(payload, 6),
+        gamma: gamma(payload[28]),
+        max_luminance: luminance(21),
+        max_luminance_10_percent: luminance(23),
+        min_luminance: luminance(25),
+    }
+}
+
+/// Decodes an IEEE 754 half-precision float
+fn half_float(value: u16) -> f32 {
+    let sign = if value & 0x8000 != 0 { -1.0 } else { 1.0 };
+    let exponent = ((value >> 10) & 0x1f) as i32;
+    let mantissa = (value & 0x03ff) as f32;
+    match exponent {
+        0 => sign * mantissa * 2f32.powi(-24),
+        0x1f => sign * f32::INFINITY,
+        _ => sign * (1.0 + mantissa / 1024.0) * 2f32.powi(exponent - 15),
+    }
+}
+
+fn timing(data: &[u8], clock_unit_khz: u32) -> DisplayIdTiming {
+    // Fields are stored minus one, so 0xffff stands for 65536
+    let size = |index| le16(data, index) as u32 + 1;
+    let offset = |index| (le16(data, index) & 0x7fff) as u32 + 1;
+    DisplayIdTiming {
+        pixel_clock_khz: (u32::from_le_bytes([data[0], data[1], data[2], 0]) + 1) * clock_unit_khz,
+        preferred: data[3] & 0x80 != 0,
+        interlaced: data[3] & 0x10 != 0,
+        aspect_ratio: data[3] & 0x0f,
+        horizontal_active: size(4),
+        horizontal_blanking: size(6),
+        horizontal_front_porch: offset(8),
+        horizontal_sync_positive: data[9] & 0x80 != 0,
+        horizontal_sync_width: size(10),
+        vertical_active: size(12),
+        vertical_blanking: size(14),
+        vertical_front_porch: offset(16),
+        vertical_sync_positive: data[17] & 0x80 != 0,
+        vertical_sync_width: size(18),
+    }
+}
+
+fn tiled_display_topology(version: u8, payload: &[u8]) -> TiledDisplayTopology {
+    let high = payload[3];
+    TiledDisplayTopology {
+        single_enclosure: payload[0] & 0x80 != 0,
+        horizontal_tiles: ((payload[1] >> 4) | ((high >> 6) & 0x03) << 4) + 1,
+        vertical_tiles: ((payload[1] & 0x0f) | ((high >> 4) & 0x03) << 4) + 1,
+        horizontal_location: (payload[2] >> 4) | ((high >> 2) & 0x03) << 4,
+        vertical_location: (payload[2] & 0x0f) | (high & 0x03) << 4,
+        tile_width: le16(payload, 4) as u32 + 1,
+        tile_height: le16(payload, 6) as u32 + 1,
+        manufacturer: manufacturer(version, &payload[13..16]),
+        product_code: le16(payload, 16),
+        serial_number: u32::from_le_bytes([payload[18], payload[19], payload[20], payload[21]]),
+    }
+}
@@ -0,0 +1,563 @@
+//! Parsing of Extended Display Identification Data (EDID), as returned by `Monitor::edid`.
+//!
+//! The base block is decoded into an [Edid]. Extension blocks are kept as raw bytes in [Edid::extensions], and
+//! can be decoded with [Edid::cta_extensions] and [Edid::displayid_extensions].
+//!
+//! EDID data of doubtful origin, for example read through a dock, can be checked and repaired with [validate].
+
+mod cta;
+mod displayid;
+mod validate;
+
+pub use cta::*;
+pub use displayid::*;
+pub use validate::*;
+
+use std::fmt;
+use thiserror::Error;
+
+/// Size of an EDID block, base or extension
+pub const BLOCK_LEN: usize = 128;
+
+/// Fixed pattern every EDID base block starts with
+pub const HEADER: [u8; 8] = [0x00, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0x00];
+
+/// Errors preventing EDID data from being parsed at all
+#[derive(Error, Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum EdidError {
+    /// The data is shorter than an EDID base block
+    #[error("EDID data too short: {0} bytes")]
+    TooShort(usize),
+    /// The data does not start with the EDID header
+    #[error("Invalid EDID header")]
+    InvalidHeader,
+}
+
+/// A parsed EDID base block
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Edid {
+    /// Three-letter PNP ID of the manufacturer, e.g. `DEL`
+    pub manufacturer: String,
+    /// Manufacturer product code
+    pub product_code: u16,
+    /// Numeric serial number, zero if not used
+    pub serial_number: u32,
+    /// Week and year of manufacture, or model year
+    pub manufacture_date: ManufactureDate,
+    /// EDID version
+    pub version: u8,
+    /// EDID revision
+    pub revision: u8,
+    /// Video input definition
+    pub video_input: VideoInput,
+    /// Physical screen size, if the display reports one
+    pub physical_size: Option<PhysicalSize>,
+    /// Display transfer characteristic, if defined in the base block
+    pub gamma: Option<f32>,
+    /// Supported features
+    pub features: Features,
+    /// Color characteristics
+    pub chromaticity: Chromaticity,
+    /// Supported legacy VESA timings
+    pub established_timings: Vec<StandardTiming>,
+    /// Standard timings from the base block
+    pub standard_timings: Vec<StandardTiming>,
+    /// The four 18-byte descriptors of the base block
+    pub descriptors: Vec<Descriptor>,
+    /// Number of extension blocks announced by the base block
+    pub extension_count: u8,
+    /// Extension blocks following the base block, as raw bytes
+    pub extensions: Vec<Vec<u8>>,
+}
+
+/// When the display was manufactured
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum ManufactureDate {
+    /// Week (if specified) and year of manufacture
+    Manufactured {
+        /// Week of manufacture, from 1 to 54
+        week: Option<u8>,
+        /// Year of manufacture
+        year: u16,
+    },
+    /// Model year, when the manufacture date is not reported
+    ModelYear(u16),
+}
+
+/// Video input definition
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum VideoInput {
+    /// Digital input
+    Digital {
+        /// Bits per color channel, if defined
+        bit_depth: Option<u8>,
+        /// Digital interface standard
+        interface: DigitalInterface,
+    },
+    /// Analog input
+    Analog {
+        /// Video white and sync levels, relative to blank: bits 6-5 of the input definition
+        signal_level: u8,
+        /// Composite sync, sync on green and serration flags: bits 4-0 of the input definition
+        sync_flags: u8,
+    },
+}
+
+/// Digital video interface standard
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum DigitalInterface {
+    /// Not defined
+    Undefined,
+    /// DVI
+    Dvi,
+    /// HDMI-a
+    HdmiA,
+    /// HDMI-b
+    HdmiB,
+    /// MDDI
+    Mddi,
+    /// DisplayPort
+    DisplayPort,
+    /// Reserved value
+    Reserved(u8),
+}
+
+/// Physical size of the screen
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum PhysicalSize {
+    /// Width and height in centimeters
+    Dimensions {
+        /// Horizontal size in centimeters
+        width_cm: u8,
+        /// Vertical size in centimeters
+        height_cm: u8,
+    },
+    /// Landscape aspect ratio, width divided by height
+    LandscapeAspectRatio(u8),
+    /// Portrait aspect ratio, height divided by width
+    PortraitAspectRatio(u8),
+}
+
+/// Feature support flags
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Features {
+    /// DPMS standby supported
+    pub standby: bool,
+    /// DPMS suspend supported
+    pub suspend: bool,
+    /// DPMS active-off supported
+    pub active_off: bool,
+    /// Display type, or supported color encodings for digital inputs: bits 4-3 of the feature byte
+    pub display_type: u8,
+    /// sRGB is the default color space
+    pub srgb_default: bool,
+    /// The first detailed timing is the native, preferred timing
+    pub preferred_timing_native: bool,
+    /// Continuous frequency display (EDID 1.4), or GTF support (EDID 1.3)
+    pub continuous_frequency: bool,
+}
+
+/// CIE 1931 xy coordinates
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ChromaticityPoint {
+    /// x coordinate
+    pub x: f32,
+    /// y coordinate
+    pub y: f32,
+}
+
+/// Chromaticity coordinates of the primaries and of the white point
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Chromaticity {
+    /// Red primary
+    pub red: ChromaticityPoint,
+    /// Green primary
+    pub green: ChromaticityPoint,
+    /// Blue primary
+    pub blue: ChromaticityPoint,
+    /// Default white point
+    pub white: ChromaticityPoint,
+}
+
+/// A video mode identified by its resolution and refresh rate
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct StandardTiming {
+    /// Horizontal addressable pixels
+    pub width: u16,
+    /// Vertical addressable lines
+    pub height: u16,
+    /// Refresh rate in Hz
+    pub refresh_rate: u8,
+}
+
+/// A fully specified video mode
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct DetailedTiming {
+    /// Pixel clock in kHz
+    pub pixel_clock_khz: u32,
+    /// Horizontal addressable pixels
+    pub horizontal_active: u16,
+    /// Horizontal blanking pixels
+    pub horizontal_blanking: u16,
+    /// Horizontal front porch in pixels
+    pub horizontal_front_porch: u16,
+    /// Horizontal sync pulse width in pixels
+    pub horizontal_sync_width: u16,
+    /// Vertical addressable lines
+    pub vertical_active: u16,
+    /// Vertical blanking lines
+    pub vertical_blanking: u16,
+    /// Vertical front porch in lines
+    pub vertical_front_porch: u16,
+    /// Vertical sync pulse width in lines
+    pub vertical_sync_width: u16,
+    /// Horizontal image size in millimeters
+    pub horizontal_image_size_mm: u16,
+    /// Vertical image size in millimeters
+    pub vertical_image_size_mm: u16,
+    /// Horizontal border pixels on each side
+    pub horizontal_border: u8,
+    /// Vertical border lines on each side
+    pub vertical_border: u8,
+    /// Interlaced video mode
+    pub interlaced: bool,
+    /// Stereo mode and sync definition: the raw flags byte
+    pub flags: u8,
+}
+
+impl DetailedTiming {
+    /// Parses an 18-byte detailed timing descriptor, returning `None` for display descriptors
+    pub fn parse(data: &[u8]) -> Option<Self> {
+        if data.len() < 18 || (data[0] == 0 && data[1] == 0) {
+            return None;
+        }
+        Some(DetailedTiming {
+            pixel_clock_khz: u16::from_le_bytes([data[0], data[1]]) as u32 * 10,
+            horizontal_active: data[2] as u16 | ((data[4] as u16 >> 4) << 8),
+            horizontal_blanking: data[3] as u16 | ((data[4] as u16 & 0x0f) << 8),
+            vertical_active: data[5] as u16 | ((data[7] as u16 >> 4) << 8),
+            vertical_blanking: data[6] as u16 | ((data[7] as u16 & 0x0f) << 8),
+            horizontal_front_porch: data[8] as u16 | (((data[11] as u16 >> 6) & 0x03) << 8),
+            horizontal_sync_width: data[9] as u16 | (((data[11] as u16 >> 4) & 0x03) << 8),
+            vertical_front_porch: (data[10] as u16 >> 4) | (((data[11] as u16 >> 2) & 0x03) << 4),
+            vertical_sync_width: (data[10] as u16 & 0x0f) | ((data[11] as u16 & 0x03) << 4),
+            horizontal_image_size_mm: data[12] as u16 | ((data[14] as u16 >> 4) << 8),
+            vertical_image_size_mm: data[13] as u16 | ((data[14] as u16 & 0x0f) << 8),
+            horizontal_border: data[15],
+            vertical_border: data[16],
+            interlaced: data[17] & 0x80 != 0,
+            flags: data[17],
+        })
+    }
+
+    /// Vertical refresh rate in Hz
+    pub fn refresh_rate(&self) -> f64 {
+        let total = (self.horizontal_active as u64 + self.horizontal_blanking as u64)
+            * (self.vertical_active as u64 + self.vertical_blanking as u64);
+        if total == 0 {
+            return 0.0;
+        }
+        self.pixel_clock_khz as f64 * 1000.0 / total as f64
+    }
+}
+
+/// Display range limits
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct RangeLimits {
+    /// Minimum vertical rate in Hz
+    pub min_vertical_hz: u16,
+    /// Maximum vertical rate in Hz
+    pub max_vertical_hz: u16,
+    /// Minimum horizontal rate in kHz
+    pub min_horizontal_khz: u16,
+    /// Maximum horizontal rate in kHz
+    pub max_horizontal_khz: u16,
+    /// Maximum pixel clock in MHz
+    pub max_pixel_clock_mhz: u16,
+}
+
+/// One of the 18-byte descriptors of the base block
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Descriptor {
+    /// A detailed timing
+    DetailedTiming(DetailedTiming),
+    /// Display product serial number (tag 0xFF)
+    SerialNumber(String),
+    /// Alphanumeric data string (tag 0xFE)
+    Text(String),
+    /// Display range limits (tag 0xFD)
+    RangeLimits(RangeLimits),
+    /// Display product name (tag 0xFC)
+    ProductName(String),
+    /// Dummy descriptor (tag 0x10)
+    Dummy,
+    /// Any other display descriptor, with its tag and 13 bytes of data
+    Other(u8, Vec<u8>),
+}
+
+impl Descriptor {
+    /// Parses an 18-byte descriptor, which must be at least 18 bytes long
+    pub fn parse(data: &[u8]) -> Self {
+        if let Some(timing) = DetailedTiming::parse(data) {
+            return Descriptor::DetailedTiming(timing);
+        }
+        let payload = &data[5..18];
+        match data[3] {
+            0xff => Descriptor::SerialNumber(descriptor_string(payload)),
+            0xfe => Descriptor::Text(descriptor_string(payload)),
+            0xfd => Descriptor::RangeLimits(range_limits(data[4], payload)),
+            0xfc => Descriptor::ProductName(descriptor_string(payload)),
+            0x10 => Descriptor::Dummy,
+            tag => Descriptor::Other(tag, payload.to_vec()),
+        }
+    }
+}
+
+impl Edid {
+    /// Parses EDID data: a base block, optionally followed by extension blocks.
+    ///
+    /// Parsing is lenient: checksums are not verified, and extension blocks are kept as far as the data goes.
+    pub fn parse(data: &[u8]) -> Result<Self, EdidError> {
+        if data.len() < BLOCK_LEN {
+            return Err(EdidError::TooShort(data.len()));
+        }
+        if data[..8] != HEADER {
+            return Err(EdidError::InvalidHeader);
+        }
+        let revision = data[19];
+        Ok(Edid {
+            manufacturer: pnp_id(u16::from_be_bytes([data[8], data[9]])),
+            product_code: u16::from_le_bytes([data[10], data[11]]),
+            serial_number: u32::from_le_bytes([data[12], data[13], data[14], data[15]]),
+            manufacture_date: match (data[16], data[17] as u16 + 1990) {
+                (0xff, year) => ManufactureDate::ModelYear(year),
+                (0, year) => ManufactureDate::Manufactured { week: None, year },
+                (week, year) => ManufactureDate::Manufactured { week: Some(week), year },
+            },
+            version: data[18],
+            revision,
+            video_input: video_input(data[20]),
+            physical_size: match (data[21], data[22]) {
+                (0, 0) => None,
+                (ratio, 0) => Some(PhysicalSize::LandscapeAspectRatio(ratio)),
+                (0, ratio) => Some(PhysicalSize::PortraitAspectRatio(ratio)),
+                (width_cm, height_cm) => Some(PhysicalSize::Dimensions { width_cm, height_cm }),
+            },
+            gamma: match data[23] {
+                0xff => None,
+                gamma => Some((gamma as f32 + 100.0) / 100.0),
+            },
+            features: Features {
+                standby: data[24] & 0x80 != 0,
+                suspend: data[24] & 0x40 != 0,
+                active_off: data[24] & 0x20 != 0,
+                display_type: (data[24] >> 3) & 0x03,
+                srgb_default: data[24] & 0x04 != 0,
+                preferred_timing_native: data[24] & 0x02 != 0,
+                continuous_frequency: data[24] & 0x01 != 0,
+            },
+            chromaticity: chromaticity(&data[25..35]),
+            established_timings: established_timings(&data[35..38]),
+            standard_timings: data[38..54]
+                .chunks(2)
+                .filter_map(|timing| standard_timing(timing, revision))
+                .collect(),
+            descriptors: data[54..126].chunks(18).map(Descriptor::parse).collect(),
+            extension_count: data[126],
+            extensions: data[BLOCK_LEN..]
+                .chunks(BLOCK_LEN)
+                .filter(|block| block.len() == BLOCK_LEN)
+                .map(|block| block.to_vec())
+                .collect(),
+        })
+    }
+
+    /// Detailed timings of the base block, the first one being the preferred timing
+    pub fn detailed_timings(&self) -> impl Iterator<Item = &DetailedTiming> {
+        self.descriptors.iter().filter_map(|descriptor| match descriptor {
+            Descriptor::DetailedTiming(timing) => Some(timing),
+            _ => None,
+        })
+    }
+
+    /// Display product name, from the product name descriptor
+    pub fn product_name(&self) -> Option<&str> {
+        self.descriptors.iter().find_map(|descriptor| match descriptor {
+            Descriptor::ProductName(name) => Some(name.as_str()),
+            _ => None,
+        })
+    }
+
+    /// Display serial number, from the serial number descriptor
+    pub fn serial_string(&self) -> Option<&str> {
+        self.descriptors.iter().find_map(|descriptor| match descriptor {
+            Descriptor::SerialNumber(serial) => Some(serial.as_str()),
+            _ => None,
+        })
+    }
+
+    /// CTA-861 extension blocks, decoded
+    pub fn cta_extensions(&self) -> Vec<CtaExtension> {
+        self.extensions
+            .iter()
+            .filter_map(|block| CtaExtension::parse(block))
+            .collect()
+    }
+
+    /// DisplayID extension blocks, decoded
+    pub fn displayid_extensions(&self) -> Vec<DisplayIdExtension> {
+        self.extensions
+            .iter()
+            .filter_map(|block| DisplayIdExtension::parse(block))
+            .collect()
+    }
+
+    /// Display range limits, if the base block has a range limits descriptor
+    pub fn range_limits(&self) -> Option<&RangeLimits> {
+        self.descriptors.iter().find_map(|descriptor| match descriptor {
+            Descriptor::RangeLimits(limits) => Some(limits),
+            _ => None,
+        })
+    }
+}
+
+impl fmt::Display for StandardTiming {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}x{}@{}", self.width, self.height, self.refresh_rate)
+    }
+}
+
+/// Decodes the compressed ASCII manufacturer ID
+fn pnp_id(id: u16) -> String {
+    [(id >> 10) & 0x1f, (id >> 5) & 0x1f, id & 0x1f]
+        .iter()
+        .map(|letter| (b'A' - 1 + *letter as u8) as char)
+        .collect()
+}
+
+fn video_input(input: u8) -> VideoInput {
+    if input & 0x80 == 0 {
+        return VideoInput::Analog {
+            signal_level: (input >> 5) & 0x03,
+            sync_flags: input & 0x1f,
+        };
+    }
+    VideoInput::Digital {
+        bit_depth: match (input >> 4) & 0x07 {
+            0 | 7 => None,
+            depth => Some(4 + depth * 2),
+        },
+        interface: match input & 0x0f {
+            0 => DigitalInterface::Undefined,
+            1 => DigitalInterface::Dvi,
+            2 => DigitalInterface::HdmiA,
+            3 => DigitalInterface::HdmiB,
+            4 => DigitalInterface::Mddi,
+            5 => DigitalInterface::DisplayPort,
+            other => DigitalInterface::Reserved(other),
+        },
+    }
+}
+
+fn chromaticity(data: &[u8]) -> Chromaticity {
+    let coordinate = |high: u8, low: u8| ((high as u16) << 2 | low as u16) as f32 / 1024.0;
+    let point = |x_high, x_low, y_high, y_low| ChromaticityPoint {
+        x: coordinate(x_high, x_low),
+        y: coordinate(y_high, y_low),
+    };
+    Chromaticity {
+        red: point(data[2], (data[0] >> 6) & 0x03, data[3], (data[0] >> 4) & 0x03),
+        green: point(data[4], (data[0] >> 2) & 0x03, data[5], data[0] & 0x03),
+        blue: point(data[6], (data[1] >> 6) & 0x03, data[7], (data[1] >> 4) & 0x03),
+        white: point(data[8], (data[1] >> 2) & 0x03, data[9], data[1] & 0x03),
+    }
+}
+
+/// Established timings I and II, in bit order from byte 0x23 bit 7 to byte 0x25 bit 7
+const ESTABLISHED_TIMINGS: [(u16, u16, u8); 17] = [
+    (720, 400, 70),
+    (720, 400, 88),
+    (640, 480, 60),
+    (640, 480, 67),
+    (640, 480, 72),
+    (640, 480, 75),
+    (800, 600, 56),
+    (800, 600, 60),
+    (800, 600, 72),
+    (800, 600, 75),
+    (832, 624, 75),
+    (1024, 768, 87),
+    (1024, 768, 60),
+    (1024, 768, 70),
+    (1024, 768, 75),
+    (1280, 1024, 75),
+    (1152, 870, 75),
+];
+
+fn established_timings(data: &[u8]) -> Vec<StandardTiming> {
+    ESTABLISHED_TIMINGS
+        .iter()
+        .enumerate()
+        .filter(|(bit, _)| data[bit / 8] & (0x80 >> (bit % 8)) != 0)
+        .map(|(_, &(width, height, refresh_rate))| StandardTiming {
+            width,
+            height,
+            refresh_rate,
+        })
+        .collect()
+}
+
+/// Decodes a 2-byte standard timing, as found in the base block and in standard timing descriptors
+pub(crate) fn standard_timing(data: &[u8], revision: u8) -> Option<StandardTiming> {
+    if data[0] == 0x00 || (data[0] == 0x01 && data[1] == 0x01) {
+        return None;
+    }
+    let width = (data[0] as u16 + 31) * 8;
+    let height = match data[1] >> 6 {
+        // Before EDID 1.3, this aspect ratio was 1:1
+        0 if revision < 3 => width,
+        0 => width * 10 / 16,
+        1 => width * 3 / 4,
+        2 => width * 4 / 5,
+        _ => width * 9 / 16,
+    };
+    Some(StandardTiming {
+        width,
+        height,
+        refresh_rate: (data[1] & 0x3f) + 60,
+    })
+}
+
+fn range_limits(offsets: u8, data: &[u8]) -> RangeLimits {
+    let offset = |bits: u8, max: bool| match (offsets >> bits) & 0x03 {
+        0x02 if max => 255,
+        0x03 => 255,
+        _ => 0,
+    };
+    RangeLimits {
+        min_vertical_hz: data[0] as u16 + offset(0, false),
+        max_vertical_hz: data[1] as u16 + offset(0, true),
+        min_horizontal_khz: data[2] as u16 + offset(2, false),
+        max_horizontal_khz: data[3] as u16 + offset(2, true),
+        max_pixel_clock_mhz: data[4] as u16 * 10,
+    }
+}
+
+/// Decodes a descriptor string: ASCII, terminated by a line feed and padded with spaces
+fn descriptor_string(data: &[u8]) -> String {
+    let end = data.iter().position(|&c| c == 0x0a).unwrap_or(data.len());
+    String::from_utf8_lossy(&data[..end]).trim_end().to_string()
+}
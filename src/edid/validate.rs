@@ -0,0 +1,301 @@
+use crate::edid::{DetailedTiming, BLOCK_LEN, HEADER};
+use std::fmt;
+
+/// How serious a [Finding] is
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Severity {
+    /// Unusual but harmless
+    Info,
+    /// Not compliant, but the data can still be used
+    Warning,
+    /// The data is corrupt and should not be trusted as is
+    Error,
+}
+
+/// A problem found while validating EDID data
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Issue {
+    /// The data is shorter than a base block
+    TooShort(usize),
+    /// The data does not end on a block boundary, leaving this many extra bytes
+    TrailingBytes(usize),
+    /// The base block does not start with the EDID header
+    InvalidHeader,
+    /// The bytes of a block do not sum to zero
+    InvalidChecksum {
+        /// Checksum stored in the block
+        stored: u8,
+        /// Checksum the block should have
+        expected: u8,
+    },
+    /// The base block announces a different number of extension blocks than the data contains
+    ExtensionCountMismatch {
+        /// Extension count of the base block
+        declared: u8,
+        /// Number of extension blocks present
+        present: usize,
+    },
+    /// EDID version other than 1
+    UnsupportedVersion(u8),
+    /// The manufacturer ID is not made of three letters
+    InvalidManufacturerId(u16),
+    /// The first descriptor is not a detailed timing, although EDID 1.3 and later require a preferred timing
+    MissingPreferredTiming,
+    /// A detailed timing has no addressable pixels or lines
+    InvalidDetailedTiming,
+    /// A display descriptor has non-zero reserved bytes
+    InvalidDescriptorHeader,
+    /// A descriptor string is not printable ASCII terminated by a line feed and padded with spaces
+    InvalidDescriptorString,
+    /// Display range limits with a minimum above the maximum
+    InvalidRangeLimits,
+    /// Extension block with a tag this crate does not know
+    UnknownExtension(u8),
+}
+
+impl fmt::Display for Issue {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Issue::TooShort(len) => write!(f, "data too short: {} bytes", len),
+            Issue::TrailingBytes(len) => write!(f, "{} bytes after the last complete block", len),
+            Issue::InvalidHeader => write!(f, "invalid header"),
+            Issue::InvalidChecksum { stored, expected } => {
+                write!(f, "invalid checksum {:#04x}, expected {:#04x}", stored, expected)
+            }
+            Issue::ExtensionCountMismatch { declared, present } => {
+                write!(f, "{} extension blocks announced, {} present", declared, present)
+            }
+            Issue::UnsupportedVersion(version) => write!(f, "unsupported EDID version {}", version),
+            Issue::InvalidManufacturerId(id) => write!(f, "invalid manufacturer ID {:#06x}", id),
+            Issue::MissingPreferredTiming => write!(f, "first descriptor is not a preferred timing"),
+            Issue::InvalidDetailedTiming => write!(f, "detailed timing without addressable pixels"),
+            Issue::InvalidDescriptorHeader => write!(f, "display descriptor with non-zero reserved bytes"),
+            Issue::InvalidDescriptorString => write!(f, "malformed descriptor string"),
+            Issue::InvalidRangeLimits => write!(f, "range limits minimum above maximum"),
+            Issue::UnknownExtension(tag) => write!(f, "unknown extension tag {:#04x}", tag),
+        }
+    }
+}
+
+/// Where a [Finding] was made
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Location {
+    /// The data as a whole
+    Data,
+    /// A block, by index: 0 is the base block
+    Block(usize),
+    /// One of the four descriptors of the base block, by index
+    Descriptor(usize),
+}
+
+/// A single validation result
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Finding {
+    /// How serious the issue is
+    pub severity: Severity,
+    /// Where the issue was found
+    pub location: Location,
+    /// What the issue is
+    pub issue: Issue,
+}
+
+impl fmt::Display for Finding {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let severity = match self.severity {
+            Severity::Info => "info",
+            Severity::Warning => "warning",
+            Severity::Error => "error",
+        };
+        match self.location {
+            Location::Data => write!(f, "{}: {}", severity, self.issue),
+            Location::Block(index) => write!(f, "{}: block {}: {}", severity, index, self.issue),
+            Location::Descriptor(index) => write!(f, "{}: descriptor {}: {}", severity, index, self.issue),
+        }
+    }
+}
+
+/// The result of validating EDID data with [validate]
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ValidationReport {
+    /// Everything found, in the order of the data
+    pub findings: Vec<Finding>,
+    /// A copy of the data with its header, checksums, extension count and length fixed, if any of them needed fixing
+    pub repaired: Option<Vec<u8>>,
+}
+
+impl ValidationReport {
+    /// Whether no error was found; warnings and infos are allowed
+    pub fn is_valid(&self) -> bool {
+        self.findings.iter().all(|finding| finding.severity < Severity::Error)
+    }
+
+    /// Severity of the most serious finding, `None` if there is none
+    pub fn max_severity(&self) -> Option<Severity> {
+        self.findings.iter().map(|finding| finding.severity).max()
+    }
+
+    /// Findings of at least the given severity
+    pub fn findings_at_least(&self, severity: Severity) -> impl Iterator<Item = &Finding> {
+        self.findings.iter().filter(move |finding| finding.severity >= severity)
+    }
+}
+
+/// Validates raw EDID data, such as returned by `Monitor::edid`.
+///
+/// Checks the header, the checksum of every block, that the extension count matches the blocks present, and the
+/// sanity of the base block descriptors. Unlike [crate::edid::Edid::parse], this never fails: corrupt data is
+/// reported as findings of [Severity::Error].
+pub fn validate(data: &[u8]) -> ValidationReport {
+    let mut report = ValidationReport::default();
+    let mut push = |severity, location, issue| {
+        report.findings.push(Finding {
+            severity,
+            location,
+            issue,
+        })
+    };
+    if data.len() < BLOCK_LEN {
+        push(Severity::Error, Location::Data, Issue::TooShort(data.len()));
+        return report;
+    }
+
+    let trailing = data.len() % BLOCK_LEN;
+    let mut repaired = data[..data.len() - trailing].to_vec();
+    let mut repair_needed = false;
+    if trailing != 0 {
+        push(Severity::Warning, Location::Data, Issue::TrailingBytes(trailing));
+        repair_needed = true;
+    }
+
+    let base = &data[..BLOCK_LEN];
+    if base[..8] != HEADER {
+        push(Severity::Error, Location::Block(0), Issue::InvalidHeader);
+        repaired[..8].copy_from_slice(&HEADER);
+        repair_needed = true;
+    }
+    let manufacturer = u16::from_be_bytes([base[8], base[9]]);
+    let letters = [manufacturer >> 10, manufacturer >> 5, manufacturer].map(|letter| letter & 0x1f);
+    if manufacturer & 0x8000 != 0 || letters.iter().any(|&letter| !(1..=26).contains(&letter)) {
+        push(
+            Severity::Warning,
+            Location::Block(0),
+            Issue::InvalidManufacturerId(manufacturer),
+        );
+    }
+    if base[18] != 1 {
+        push(
+            Severity::Warning,
+            Location::Block(0),
+            Issue::UnsupportedVersion(base[18]),
+        );
+    }
+
+    for (index, descriptor) in base[54..126].chunks(18).enumerate() {
+        for issue in descriptor_issues(descriptor) {
+            push(Severity::Warning, Location::Descriptor(index), issue);
+        }
+    }
+    if base[18] == 1 && base[19] >= 3 && DetailedTiming::parse(&base[54..72]).is_none() {
+        push(
+            Severity::Warning,
+            Location::Descriptor(0),
+            Issue::MissingPreferredTiming,
+        );
+    }
+
+    let present = repaired.len() / BLOCK_LEN - 1;
+    let declared = base[126];
+    if declared as usize != present {
+        // Missing blocks mean the data was cut short, extra blocks are merely unannounced
+        let severity = if declared as usize > present {
+            Severity::Error
+        } else {
+            Severity::Warning
+        };
+        push(
+            severity,
+            Location::Block(0),
+            Issue::ExtensionCountMismatch { declared, present },
+        );
+        repaired[126] = present.min(u8::MAX as usize) as u8;
+        repair_needed = true;
+    }
+
+    for (index, block) in data.chunks_exact(BLOCK_LEN).enumerate() {
+        if index > 0 && !matches!(block[0], 0x02 | 0x10 | 0x40 | 0x50 | 0x60 | 0x70 | 0xf0 | 0xff) {
+            push(
+                Severity::Info,
+                Location::Block(index),
+                Issue::UnknownExtension(block[0]),
+            );
+        }
+        let sum = block.iter().fold(0u8, |sum, byte| sum.wrapping_add(*byte));
+        if sum != 0 {
+            let stored = block[BLOCK_LEN - 1];
+            let expected = stored.wrapping_sub(sum);
+            push(
+                Severity::Error,
+                Location::Block(index),
+                Issue::InvalidChecksum { stored, expected },
+            );
+        }
+    }
+
+    // Checksums are recomputed last, as the repairs above change the base block
+    for block in repaired.chunks_exact_mut(BLOCK_LEN) {
+        let sum = block[..BLOCK_LEN - 1]
+            .iter()
+            .fold(0u8, |sum, byte| sum.wrapping_add(*byte));
+        let checksum = 0u8.wrapping_sub(sum);
+        if block[BLOCK_LEN - 1] != checksum {
+            block[BLOCK_LEN - 1] = checksum;
+            repair_needed = true;
+        }
+    }
+    if repair_needed {
+        report.repaired = Some(repaired);
+    }
+    report
+}
+
+fn descriptor_issues(descriptor: &[u8]) -> Vec<Issue> {
+    if descriptor[0] != 0 || descriptor[1] != 0 {
+        return match DetailedTiming::parse(descriptor) {
+            Some(timing) if timing.horizontal_active != 0 && timing.vertical_active != 0 => vec![],
+            _ => vec![Issue::InvalidDetailedTiming],
+        };
+    }
+    let mut issues = Vec::new();
+    if descriptor[2] != 0 {
+        issues.push(Issue::InvalidDescriptorHeader);
+    }
+    let payload = &descriptor[5..18];
+    match descriptor[3] {
+        0xfc | 0xfe | 0xff if !valid_descriptor_string(payload) => issues.push(Issue::InvalidDescriptorString),
+        0xfd => {
+            let offsets = descriptor[4];
+            let (vertical_min, vertical_max) = (payload[0] as u16, payload[1] as u16);
+            let (horizontal_min, horizontal_max) = (payload[2] as u16, payload[3] as u16);
+            // With an offset on the maximum only, raw values cannot be compared
+            let vertical_bad = offsets & 0x03 != 0x02 && vertical_min > vertical_max;
+            let horizontal_bad = (offsets >> 2) & 0x03 != 0x02 && horizontal_min > horizontal_max;
+            if vertical_bad || horizontal_bad {
+                issues.push(Issue::InvalidRangeLimits);
+            }
+        }
+        _ => {}
+    }
+    issues
+}
+
+/// Whether a descriptor string is printable ASCII, followed by a line feed and space padding if shorter than 13 bytes
+fn valid_descriptor_string(data: &[u8]) -> bool {
+    let end = data.iter().position(|&c| c == 0x0a).unwrap_or(data.len());
+    data[..end].iter().all(|&c| (0x20..0x7f).contains(&c))
+        && data.get(end + 1..).unwrap_or(&[]).iter().all(|&c| c == 0x20)
+}
@@ -1,9 +1,22 @@
+#[cfg(target_os = "macos")]
 use core_graphics::base::CGError;
 use ddc::ErrorCode;
+#[cfg(target_os = "macos")]
 use io_kit_sys::ret::kIOReturnSuccess;
+#[cfg(target_os = "macos")]
 use mach2::kern_return::{kern_return_t, KERN_FAILURE};
+use std::fmt;
+use std::str::FromStr;
 use thiserror::Error;
 
+#[cfg(not(target_os = "macos"))]
+#[allow(non_camel_case_types)]
+type kern_return_t = i32;
+#[cfg(not(target_os = "macos"))]
+type CGError = i32;
+#[cfg(not(target_os = "macos"))]
+const KERN_FAILURE: kern_return_t = 5;
+
 /// An error that can occur during DDC/CI communication with a monitor
 #[derive(Error, Debug)]
 pub enum Error {
@@ -22,8 +35,133 @@ pub enum Error {
     /// Display location not found
     #[error("Display location not found")]
     DisplayLocationNotFound,
+    /// The monitor replied with a DDC/CI null message: it is busy, or cannot answer this request right now
+    #[error("Monitor is busy")]
+    MonitorBusy,
+    /// The reply did not come from the device the request was sent to
+    #[error("Reply from unexpected source address {actual:#04x}, expected {expected:#04x}")]
+    SourceAddressMismatch {
+        /// Source address of the device the request was sent to
+        expected: u8,
+        /// Source address found in the reply
+        actual: u8,
+    },
+    /// The reply does not answer the request that was sent, for example a stale reply to a previous command
+    #[error("Reply with unexpected opcode {actual:#04x}, expected {expected:#04x}")]
+    OpcodeMismatch {
+        /// Opcode answering the request
+        expected: u8,
+        /// Opcode found in the reply
+        actual: u8,
+    },
+}
+
+/// The kind of an [Error], without the details it carries
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum ErrorKind {
+    /// [Error::CoreGraphics]
+    CoreGraphics,
+    /// [Error::Io]
+    Io,
+    /// [Error::Ddc] with [ErrorCode::InvalidOffset]
+    InvalidOffset,
+    /// [Error::Ddc] with [ErrorCode::InvalidLength]
+    InvalidLength,
+    /// [Error::Ddc] with [ErrorCode::InvalidChecksum]
+    InvalidChecksum,
+    /// [Error::Ddc] with [ErrorCode::InvalidOpcode]
+    InvalidOpcode,
+    /// [Error::Ddc] with [ErrorCode::InvalidData]
+    InvalidData,
+    /// [Error::Ddc] with [ErrorCode::Invalid]
+    Invalid,
+    /// [Error::ServiceNotFound]
+    ServiceNotFound,
+    /// [Error::DisplayLocationNotFound]
+    DisplayLocationNotFound,
+    /// [Error::MonitorBusy]
+    MonitorBusy,
+    /// [Error::SourceAddressMismatch]
+    SourceAddressMismatch,
+    /// [Error::OpcodeMismatch]
+    OpcodeMismatch,
+}
+
+impl Error {
+    /// The kind of this error
+    pub fn kind(&self) -> ErrorKind {
+        match self {
+            Error::CoreGraphics(_) => ErrorKind::CoreGraphics,
+            Error::Io(_) => ErrorKind::Io,
+            Error::Ddc(ErrorCode::InvalidOffset) => ErrorKind::InvalidOffset,
+            Error::Ddc(ErrorCode::InvalidLength) => ErrorKind::InvalidLength,
+            Error::Ddc(ErrorCode::InvalidChecksum) => ErrorKind::InvalidChecksum,
+            Error::Ddc(ErrorCode::InvalidOpcode) => ErrorKind::InvalidOpcode,
+            Error::Ddc(ErrorCode::InvalidData) => ErrorKind::InvalidData,
+            Error::Ddc(ErrorCode::Invalid(_)) => ErrorKind::Invalid,
+            Error::ServiceNotFound => ErrorKind::ServiceNotFound,
+            Error::DisplayLocationNotFound => ErrorKind::DisplayLocationNotFound,
+            Error::MonitorBusy => ErrorKind::MonitorBusy,
+            Error::SourceAddressMismatch { .. } => ErrorKind::SourceAddressMismatch,
+            Error::OpcodeMismatch { .. } => ErrorKind::OpcodeMismatch,
+        }
+    }
+}
+
+impl ErrorKind {
+    /// Name of the kind, the same as its variant name, e.g. `MonitorBusy`. Names are used in recorded sessions and by
+    /// the `serde` implementations.
+    pub fn name(self) -> &'static str {
+        match self {
+            ErrorKind::CoreGraphics => "CoreGraphics",
+            ErrorKind::Io => "Io",
+            ErrorKind::InvalidOffset => "InvalidOffset",
+            ErrorKind::InvalidLength => "InvalidLength",
+            ErrorKind::InvalidChecksum => "InvalidChecksum",
+            ErrorKind::InvalidOpcode => "InvalidOpcode",
+            ErrorKind::InvalidData => "InvalidData",
+            ErrorKind::Invalid => "Invalid",
+            ErrorKind::ServiceNotFound => "ServiceNotFound",
+            ErrorKind::DisplayLocationNotFound => "DisplayLocationNotFound",
+            ErrorKind::MonitorBusy => "MonitorBusy",
+            ErrorKind::SourceAddressMismatch => "SourceAddressMismatch",
+            ErrorKind::OpcodeMismatch => "OpcodeMismatch",
+        }
+    }
+}
+
+impl fmt::Display for ErrorKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.name())
+    }
+}
+
+impl FromStr for ErrorKind {
+    type Err = Error;
+
+    /// Parses the name of a kind, as returned by [ErrorKind::name]
+    fn from_str(name: &str) -> Result<Self, Error> {
+        Ok(match name {
+            "CoreGraphics" => ErrorKind::CoreGraphics,
+            "Io" => ErrorKind::Io,
+            "InvalidOffset" => ErrorKind::InvalidOffset,
+            "InvalidLength" => ErrorKind::InvalidLength,
+            "InvalidChecksum" => ErrorKind::InvalidChecksum,
+            "InvalidOpcode" => ErrorKind::InvalidOpcode,
+            "InvalidData" => ErrorKind::InvalidData,
+            "Invalid" => ErrorKind::Invalid,
+            "ServiceNotFound" => ErrorKind::ServiceNotFound,
+            "DisplayLocationNotFound" => ErrorKind::DisplayLocationNotFound,
+            "MonitorBusy" => ErrorKind::MonitorBusy,
+            "SourceAddressMismatch" => ErrorKind::SourceAddressMismatch,
+            "OpcodeMismatch" => ErrorKind::OpcodeMismatch,
+            _ => return Err(ErrorCode::Invalid(format!("unknown error kind: {}", name)).into()),
+        })
+    }
 }
 
+#[cfg(target_os = "macos")]
 pub fn verify_io(result: kern_return_t) -> Result<(), Error> {
     if result == kIOReturnSuccess {
         Ok(())
@@ -44,6 +182,7 @@ impl From<ErrorCode> for Error {
     }
 }
 
+#[cfg(target_os = "macos")]
 impl From<CGError> for Error {
     fn from(error: CGError) -> Self {
         Error::CoreGraphics(error)
@@ -0,0 +1,291 @@
+use crate::error::Error;
+use crate::identity::MonitorId;
+use crate::info::MonitorInfo;
+use ddc::ErrorCode;
+use std::fmt;
+use std::str::FromStr;
+
+/// A property of a monitor that a [MonitorFilter] can test
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum FilterField {
+    /// PNP ID of the manufacturer, `vendor` or `manufacturer`
+    Vendor,
+    /// Model name, `model` or `name`
+    Model,
+    /// Serial number, `serial`
+    Serial,
+    /// Product code in hexadecimal, `product`
+    Product,
+    /// Backend: `intel`, `arm` or `custom`, `backend` or `connection`
+    Backend,
+    /// Position among the monitors being filtered, starting at 0, `index`
+    Index,
+    /// Stable identity, as written by [MonitorId], `id`
+    Id,
+    /// Description, `description`
+    Description,
+}
+
+impl FilterField {
+    /// Name of the field in selectors
+    pub fn name(self) -> &'static str {
+        match self {
+            FilterField::Vendor => "vendor",
+            FilterField::Model => "model",
+            FilterField::Serial => "serial",
+            FilterField::Product => "product",
+            FilterField::Backend => "backend",
+            FilterField::Index => "index",
+            FilterField::Id => "id",
+            FilterField::Description => "description",
+        }
+    }
+
+    fn from_name(name: &str) -> Option<Self> {
+        Some(match name.to_ascii_lowercase().as_str() {
+            "vendor" | "manufacturer" => FilterField::Vendor,
+            "model" | "name" => FilterField::Model,
+            "serial" => FilterField::Serial,
+            "product" => FilterField::Product,
+            "backend" | "connection" => FilterField::Backend,
+            "index" => FilterField::Index,
+            "id" => FilterField::Id,
+            "description" => FilterField::Description,
+            _ => return None,
+        })
+    }
+
+    /// Value of the field for a monitor, as text
+    fn text(self, index: usize, info: &MonitorInfo) -> Option<String> {
+        match self {
+            FilterField::Vendor => info.vendor.clone(),
+            FilterField::Model => info.model.clone(),
+            FilterField::Serial => info.serial.clone(),
+            FilterField::Product => info.product.map(|product| format!("{:04x}", product)),
+            FilterField::Backend => Some(info.backend.name().to_string()),
+            FilterField::Index => Some(index.to_string()),
+            FilterField::Id => info.id.as_ref().map(MonitorId::to_string),
+            FilterField::Description => Some(info.description.clone()),
+        }
+    }
+}
+
+/// How a [FilterCondition] compares a field to its value
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum FilterOperator {
+    /// `=`: the field equals the value, ignoring case
+    Equals,
+    /// `!=`: the field does not equal the value, or is unknown
+    NotEquals,
+    /// `~`: the field matches the value as a glob pattern, ignoring case. `*` matches any text and `?` any character.
+    Matches,
+    /// `!~`: the field does not match the glob pattern, or is unknown
+    NotMatches,
+}
+
+impl FilterOperator {
+    /// The operator as written in selectors
+    pub fn symbol(self) -> &'static str {
+        match self {
+            FilterOperator::Equals => "=",
+            FilterOperator::NotEquals => "!=",
+            FilterOperator::Matches => "~",
+            FilterOperator::NotMatches => "!~",
+        }
+    }
+}
+
+/// A single condition of a [MonitorFilter], such as `vendor=DEL`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FilterCondition {
+    /// Field tested
+    pub field: FilterField,
+    /// Comparison
+    pub operator: FilterOperator,
+    /// Value or pattern the field is compared to
+    pub value: String,
+}
+
+impl FilterCondition {
+    /// A condition, checking that the value is valid for the field
+    pub fn new(field: FilterField, operator: FilterOperator, value: &str) -> Result<Self, Error> {
+        let exact = matches!(operator, FilterOperator::Equals | FilterOperator::NotEquals);
+        let valid = !exact
+            || match field {
+                FilterField::Product => parse_product(value).is_some(),
+                FilterField::Backend => ["intel", "arm", "custom"]
+                    .iter()
+                    .any(|name| name.eq_ignore_ascii_case(value)),
+                FilterField::Index => value.parse::<usize>().is_ok(),
+                FilterField::Id => value.parse::<MonitorId>().is_ok(),
+                _ => true,
+            };
+        if !valid {
+            return Err(ErrorCode::Invalid(format!("invalid {} value: {}", field.name(), value)).into());
+        }
+        Ok(FilterCondition {
+            field,
+            operator,
+            value: value.to_string(),
+        })
+    }
+
+    /// Whether the monitor at position `index` described by `info` fulfills the condition
+    pub fn matches(&self, index: usize, info: &MonitorInfo) -> bool {
+        let text = self.field.text(index, info);
+        match self.operator {
+            FilterOperator::Equals => self.equals(index, info, text.as_deref()),
+            FilterOperator::NotEquals => !self.equals(index, info, text.as_deref()),
+            FilterOperator::Matches => text.is_some_and(|text| glob(&self.value, &text)),
+            FilterOperator::NotMatches => !text.is_some_and(|text| glob(&self.value, &text)),
+        }
+    }
+
+    fn equals(&self, index: usize, info: &MonitorInfo, text: Option<&str>) -> bool {
+        match self.field {
+            FilterField::Product => info.product.is_some() && info.product == parse_product(&self.value),
+            FilterField::Index => self.value.parse() == Ok(index),
+            // The location of the identity is only compared if the selector has one
+            FilterField::Id => match (&info.id, self.value.parse::<MonitorId>()) {
+                (Some(id), Ok(selected)) => {
+                    id.same_model_and_serial(&selected)
+                        && (selected.location.is_none() || id.location == selected.location)
+                }
+                _ => false,
+            },
+            _ => text.is_some_and(|text| text.eq_ignore_ascii_case(&self.value)),
+        }
+    }
+}
+
+impl fmt::Display for FilterCondition {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}{}{}", self.field.name(), self.operator.symbol(), self.value)
+    }
+}
+
+/// Selects monitors by their properties.
+///
+/// Filters are written as comma-separated conditions, all of which must hold, such as `vendor=DEL,model~U27*` or
+/// `backend=arm,serial!=ABC123`. Each condition is a field, an operator and a value: `=` and `!=` compare values
+/// ignoring case, `~` and `!~` match glob patterns where `*` matches any text and `?` any character. Fields are
+/// listed in [FilterField]. Values cannot contain commas. An empty filter selects all monitors.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct MonitorFilter {
+    conditions: Vec<FilterCondition>,
+}
+
+impl MonitorFilter {
+    /// A filter selecting all monitors
+    pub fn all() -> Self {
+        Self::default()
+    }
+
+    /// Parses a filter
+    pub fn parse(selector: &str) -> Result<Self, Error> {
+        selector.parse()
+    }
+
+    /// Add a condition
+    pub fn with_condition(mut self, condition: FilterCondition) -> Self {
+        self.conditions.push(condition);
+        self
+    }
+
+    /// Conditions of the filter
+    pub fn conditions(&self) -> &[FilterCondition] {
+        &self.conditions
+    }
+
+    /// Whether the monitor at position `index` described by `info` fulfills all conditions
+    pub fn matches(&self, index: usize, info: &MonitorInfo) -> bool {
+        self.conditions.iter().all(|condition| condition.matches(index, info))
+    }
+}
+
+impl FromStr for MonitorFilter {
+    type Err = Error;
+
+    fn from_str(selector: &str) -> Result<Self, Error> {
+        let conditions = selector
+            .split(',')
+            .map(str::trim)
+            .filter(|condition| !condition.is_empty())
+            .map(parse_condition)
+            .collect::<Result<_, _>>()?;
+        Ok(MonitorFilter { conditions })
+    }
+}
+
+impl fmt::Display for MonitorFilter {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (index, condition) in self.conditions.iter().enumerate() {
+            if index > 0 {
+                f.write_str(",")?;
+            }
+            write!(f, "{}", condition)?;
+        }
+        Ok(())
+    }
+}
+
+fn parse_condition(condition: &str) -> Result<FilterCondition, Error> {
+    let invalid = || {
+        Error::from(ErrorCode::Invalid(format!(
+            "invalid monitor filter condition: {}",
+            condition
+        )))
+    };
+    let position = condition.find(['=', '~', '!']).ok_or_else(invalid)?;
+    let (name, rest) = condition.split_at(position);
+    let field = FilterField::from_name(name.trim()).ok_or_else(invalid)?;
+    let (operator, value) = [
+        FilterOperator::NotEquals,
+        FilterOperator::NotMatches,
+        FilterOperator::Equals,
+        FilterOperator::Matches,
+    ]
+    .iter()
+    .find_map(|&operator| Some((operator, rest.strip_prefix(operator.symbol())?)))
+    .ok_or_else(invalid)?;
+    FilterCondition::new(field, operator, value.trim())
+}
+
+/// Parses a product code in hexadecimal, with or without a `0x` prefix
+fn parse_product(value: &str) -> Option<u16> {
+    let digits = value
+        .strip_prefix("0x")
+        .or_else(|| value.strip_prefix("0X"))
+        .unwrap_or(value);
+    u16::from_str_radix(digits, 16).ok()
+}
+
+/// Matches text against a glob pattern, ignoring case
+fn glob(pattern: &str, text: &str) -> bool {
+    let pattern: Vec<char> = pattern.chars().flat_map(char::to_lowercase).collect();
+    let text: Vec<char> = text.chars().flat_map(char::to_lowercase).collect();
+    // Iterative matching, backtracking to the last star only
+    let (mut p, mut t) = (0, 0);
+    let mut star: Option<(usize, usize)> = None;
+    while t < text.len() {
+        match pattern.get(p) {
+            Some('*') => {
+                star = Some((p, t));
+                p += 1;
+            }
+            Some(&c) if c == '?' || c == text[t] => {
+                p += 1;
+                t += 1;
+            }
+            _ => match star {
+                Some((star_p, star_t)) => {
+                    p = star_p + 1;
+                    t = star_t + 1;
+                    star = Some((star_p, star_t + 1));
+                }
+                None => return false,
+            },
+        }
+    }
+    pattern[p..].iter().all(|&c| c == '*')
+}
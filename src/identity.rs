@@ -0,0 +1,149 @@
+use crate::edid::Edid;
+use crate::error::Error;
+use ddc::ErrorCode;
+use std::fmt;
+use std::str::FromStr;
+
+/// An identity of a monitor that stays the same across reboots and reconnections, unlike CoreGraphics display IDs.
+///
+/// The identity comes from the EDID of the monitor: manufacturer, product code, numeric serial number and serial
+/// number descriptor. Identical monitors that do not report serial numbers have the same EDID identity, in which case
+/// the IORegistry location of the display, `IODisplayLocation`, tells them apart. The location changes when a
+/// monitor is plugged into another port, so it is only used as a tie-breaker.
+///
+/// Identities are written as `manufacturer/product/serial/serial string`, optionally followed by `/location`, the
+/// product code and numeric serial number being hexadecimal: `DEL/a1e4/31324653/#G7QYMxgwABxd`. Slashes and percent
+/// signs in the serial string are percent-encoded.
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct MonitorId {
+    /// Three-letter PNP ID of the manufacturer
+    pub manufacturer: String,
+    /// Manufacturer product code
+    pub product: u16,
+    /// Numeric serial number, zero if not used
+    pub serial: u32,
+    /// Serial number descriptor, if any
+    pub serial_string: Option<String>,
+    /// IORegistry location of the display, if known
+    pub location: Option<String>,
+}
+
+impl MonitorId {
+    /// Identity of the monitor described by raw EDID data, `None` if the data cannot be parsed
+    pub fn from_edid(edid: &[u8], location: Option<String>) -> Option<Self> {
+        let edid = Edid::parse(edid).ok()?;
+        Some(MonitorId {
+            serial_string: edid
+                .serial_string()
+                .filter(|serial| !serial.is_empty())
+                .map(str::to_string),
+            manufacturer: edid.manufacturer,
+            product: edid.product_code,
+            serial: edid.serial_number,
+            location,
+        })
+    }
+
+    /// Whether both identities have the same EDID identity, regardless of their locations
+    pub fn same_model_and_serial(&self, other: &MonitorId) -> bool {
+        self.manufacturer == other.manufacturer
+            && self.product == other.product
+            && self.serial == other.serial
+            && self.serial_string == other.serial_string
+    }
+
+    /// Index of the identity designating the same monitor as this one in `candidates`.
+    ///
+    /// The single candidate with the same EDID identity is picked, wherever it is connected. If several candidates
+    /// have the same EDID identity, the one at the same location is picked, and `None` is returned if there is none
+    /// rather than guessing.
+    pub fn position_in(&self, candidates: &[MonitorId]) -> Option<usize> {
+        let matches: Vec<usize> = (0..candidates.len())
+            .filter(|&index| self.same_model_and_serial(&candidates[index]))
+            .collect();
+        match matches.as_slice() {
+            [index] => Some(*index),
+            _ => matches
+                .into_iter()
+                .find(|&index| self.location.is_some() && candidates[index].location == self.location),
+        }
+    }
+}
+
+impl fmt::Display for MonitorId {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}/{:04x}/{:08x}/", self.manufacturer, self.product, self.serial)?;
+        if let Some(serial_string) = &self.serial_string {
+            for c in serial_string.chars() {
+                match c {
+                    '%' => f.write_str("%25")?,
+                    '/' => f.write_str("%2F")?,
+                    c => write!(f, "{}", c)?,
+                }
+            }
+        }
+        if let Some(location) = &self.location {
+            write!(f, "/{}", location)?;
+        }
+        Ok(())
+    }
+}
+
+impl FromStr for MonitorId {
+    type Err = Error;
+
+    fn from_str(id: &str) -> Result<Self, Error> {
+        let invalid = || Error::from(ErrorCode::Invalid(format!("invalid monitor id: {}", id)));
+        // The location comes last, and may contain slashes itself
+        let mut parts = id.splitn(5, '/');
+        let mut next = || parts.next().ok_or_else(invalid);
+        let manufacturer = next()?;
+        // Malformed EDIDs have manufacturer IDs with characters other than letters, such as `@` or `_`. Slashes
+        // cannot appear, as they separate the parts.
+        if manufacturer.len() != 3 || !manufacturer.chars().all(|c| c.is_ascii_graphic()) {
+            return Err(invalid());
+        }
+        let product = u16::from_str_radix(next()?, 16).map_err(|_| invalid())?;
+        let serial = u32::from_str_radix(next()?, 16).map_err(|_| invalid())?;
+        let serial_string = match next()? {
+            "" => None,
+            escaped => Some(unescape(escaped).ok_or_else(invalid)?),
+        };
+        Ok(MonitorId {
+            manufacturer: manufacturer.to_string(),
+            product,
+            serial,
+            serial_string,
+            location: parts.next().map(str::to_string),
+        })
+    }
+}
+
+/// Decodes the percent-encoded characters of a serial string
+fn unescape(escaped: &str) -> Option<String> {
+    let mut unescaped = String::with_capacity(escaped.len());
+    let mut rest = escaped;
+    while let Some(index) = rest.find('%') {
+        unescaped.push_str(&rest[..index]);
+        let code = rest.get(index + 1..index + 3)?;
+        unescaped.push(u8::from_str_radix(code, 16).ok()? as char);
+        rest = &rest[index + 3..];
+    }
+    unescaped.push_str(rest);
+    Some(unescaped)
+}
+
+#[cfg(feature = "serde")]
+impl serde::Serialize for MonitorId {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_str(self)
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de> serde::Deserialize<'de> for MonitorId {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let id = String::deserialize(deserializer)?;
+        id.parse().map_err(serde::de::Error::custom)
+    }
+}
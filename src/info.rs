@@ -0,0 +1,130 @@
+use crate::edid::{self, Edid, ManufactureDate, PhysicalSize};
+use crate::identity::MonitorId;
+use std::fmt;
+
+/// How a [Monitor](crate::Monitor) talks to its display
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "lowercase"))]
+pub enum Backend {
+    /// IOFramebuffer I2C interface, on Intel Macs
+    Intel,
+    /// IOAVService, on Apple silicon Macs
+    Arm,
+    /// A transport given to [Monitor::with_transport](crate::Monitor::with_transport)
+    #[default]
+    Custom,
+}
+
+impl Backend {
+    /// Lowercase name of the backend: `intel`, `arm` or `custom`
+    pub fn name(self) -> &'static str {
+        match self {
+            Backend::Intel => "intel",
+            Backend::Arm => "arm",
+            Backend::Custom => "custom",
+        }
+    }
+}
+
+impl fmt::Display for Backend {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.name())
+    }
+}
+
+/// A snapshot of the metadata describing a monitor, gathered once when the [Monitor](crate::Monitor) is created.
+///
+/// With the `serde` feature, the snapshot can be serialized, for example to JSON.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct MonitorInfo {
+    /// How the monitor is driven
+    pub backend: Backend,
+    /// I2C address of the DDC/CI device
+    pub i2c_address: u16,
+    /// CoreGraphics display ID, which changes across reboots and reconnections
+    pub display_id: Option<u32>,
+    /// Whether the display is built into the Mac
+    pub builtin: bool,
+    /// Three-letter PNP ID of the manufacturer, from the EDID
+    pub vendor: Option<String>,
+    /// Manufacturer product code, from the EDID
+    pub product: Option<u16>,
+    /// Model name: the product name CoreDisplay reports, or else the one in the EDID
+    pub model: Option<String>,
+    /// Serial number: the serial number string of the EDID, or else the numeric serial number
+    pub serial: Option<String>,
+    /// Description of the monitor, as returned by [Monitor::description](crate::Monitor::description)
+    pub description: String,
+    /// IORegistry location of the display, `IODisplayLocation`
+    pub location: Option<String>,
+    /// Stable identity of the monitor
+    pub id: Option<MonitorId>,
+    /// Raw EDID data
+    pub edid: Option<Vec<u8>>,
+    /// Main properties of the EDID, if it can be parsed
+    pub edid_summary: Option<EdidSummary>,
+}
+
+/// The main properties of an EDID
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct EdidSummary {
+    /// Three-letter PNP ID of the manufacturer
+    pub manufacturer: String,
+    /// Manufacturer product code
+    pub product_code: u16,
+    /// Numeric serial number, zero if not used
+    pub serial_number: u32,
+    /// Serial number descriptor
+    pub serial_string: Option<String>,
+    /// Product name descriptor
+    pub product_name: Option<String>,
+    /// Year of manufacture, or model year
+    pub year: u16,
+    /// Week of manufacture, if specified
+    pub week: Option<u8>,
+    /// EDID version and revision, e.g. `1.4`
+    pub version: String,
+    /// Physical width and height of the screen in centimeters
+    pub size_cm: Option<(u8, u8)>,
+    /// Active pixels of the preferred timing, usually the native resolution
+    pub native_resolution: Option<(u16, u16)>,
+    /// Number of extension blocks
+    pub extensions: u8,
+    /// Whether the EDID passes validation without errors
+    pub valid: bool,
+}
+
+impl EdidSummary {
+    /// Summary of raw EDID data, `None` if it cannot be parsed
+    pub fn parse(data: &[u8]) -> Option<Self> {
+        let parsed = Edid::parse(data).ok()?;
+        let (year, week) = match parsed.manufacture_date {
+            ManufactureDate::Manufactured { week, year } => (year, week),
+            ManufactureDate::ModelYear(year) => (year, None),
+        };
+        let native_resolution = parsed
+            .detailed_timings()
+            .next()
+            .map(|timing| (timing.horizontal_active, timing.vertical_active));
+        Some(EdidSummary {
+            serial_string: parsed.serial_string().map(str::to_string),
+            product_name: parsed.product_name().map(str::to_string),
+            version: format!("{}.{}", parsed.version, parsed.revision),
+            size_cm: match parsed.physical_size {
+                Some(PhysicalSize::Dimensions { width_cm, height_cm }) => Some((width_cm, height_cm)),
+                _ => None,
+            },
+            native_resolution,
+            extensions: parsed.extension_count,
+            valid: edid::validate(data).is_valid(),
+            manufacturer: parsed.manufacturer,
+            product_code: parsed.product_code,
+            serial_number: parsed.serial_number,
+            year,
+            week,
+        })
+    }
+}
@@ -0,0 +1,167 @@
+use crate::edid::Edid;
+use crate::vcp::InputSource;
+
+/// A video input of a monitor: its name, and the Input Select (VCP 0x60) value selecting it
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct InputEntry {
+    /// Display name, e.g. `HDMI 1`
+    pub name: String,
+    /// Other names the input can be selected by, e.g. `hdmi1`
+    pub aliases: Vec<String>,
+    /// Input Select value
+    pub value: u8,
+}
+
+/// Inputs that a vendor, or one of its products, selects with values other than the MCCS ones
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct VendorOverride {
+    /// Three-letter PNP ID of the manufacturer, as found in the EDID
+    pub manufacturer: &'static str,
+    /// Product code the override applies to, or `None` for all products of the manufacturer
+    pub product: Option<u16>,
+    /// Input names and their Input Select values
+    pub inputs: &'static [(&'static str, u8)],
+}
+
+/// Known vendor overrides. Vendor-wide overrides come before product specific ones, which take precedence.
+///
+/// Only values that Input Select (VCP 0x60) accepts belong here. Some vendors, such as LG, switch inputs through a
+/// vendor specific VCP code instead, which this table does not cover.
+pub const VENDOR_OVERRIDES: &[VendorOverride] = &[
+    // Dell selects USB-C (DisplayPort alternate mode) inputs with a value outside the MCCS table
+    VendorOverride {
+        manufacturer: "DEL",
+        product: None,
+        inputs: &[("USB-C", 0x1b)],
+    },
+];
+
+/// The inputs of a monitor, to switch inputs by name.
+///
+/// The table starts from the MCCS standard inputs. Vendor overrides replace the value of inputs of the same name,
+/// or add inputs the standard does not define. Names are matched ignoring case, spaces, dashes and underscores, and
+/// a missing input number means input 1: `usb-c`, `USB C`, `dp1`, `displayport` and `HDMI-2` all work.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct InputTable {
+    entries: Vec<InputEntry>,
+}
+
+impl Default for InputTable {
+    fn default() -> Self {
+        Self::standard()
+    }
+}
+
+impl InputTable {
+    /// The MCCS standard inputs
+    pub fn standard() -> Self {
+        let entries = InputSource::ALL
+            .iter()
+            .map(|&input| {
+                let name = input.name();
+                let aliases = [("DisplayPort", "DP"), ("VGA", "Analog"), ("DVI", "Digital")]
+                    .iter()
+                    .filter_map(|(prefix, alias)| {
+                        name.strip_prefix(prefix).map(|number| format!("{}{}", alias, number))
+                    })
+                    .collect();
+                InputEntry {
+                    name,
+                    aliases,
+                    value: input.value(),
+                }
+            })
+            .collect();
+        InputTable { entries }
+    }
+
+    /// The inputs of a monitor model: the standard inputs with the known overrides of its vendor and product applied
+    pub fn for_product(manufacturer: &str, product: u16) -> Self {
+        let mut overrides: Vec<_> = VENDOR_OVERRIDES
+            .iter()
+            .filter(|vendor| vendor.manufacturer.eq_ignore_ascii_case(manufacturer))
+            .filter(|vendor| vendor.product.is_none() || vendor.product == Some(product))
+            .collect();
+        // Product specific overrides are applied last, so that they win
+        overrides.sort_by_key(|vendor| vendor.product.is_some());
+        overrides
+            .iter()
+            .flat_map(|vendor| vendor.inputs.iter())
+            .fold(Self::standard(), |table, &(name, value)| table.with_input(name, value))
+    }
+
+    /// The inputs of the monitor described by raw EDID data, the standard inputs if the data cannot be parsed
+    pub fn for_edid(edid: &[u8]) -> Self {
+        match Edid::parse(edid) {
+            Ok(edid) => Self::for_product(&edid.manufacturer, edid.product_code),
+            Err(_) => Self::standard(),
+        }
+    }
+
+    /// Set the value of an input, adding the input if the table does not have it yet
+    pub fn with_input(mut self, name: &str, value: u8) -> Self {
+        match self.entries.iter_mut().find(|entry| entry.matches(name)) {
+            Some(entry) => entry.value = value,
+            None => self.entries.push(InputEntry {
+                name: name.to_string(),
+                aliases: Vec::new(),
+                value,
+            }),
+        }
+        self
+    }
+
+    /// Add another name an input can be selected by
+    pub fn with_alias(mut self, name: &str, alias: &str) -> Self {
+        if let Some(entry) = self.entries.iter_mut().find(|entry| entry.matches(name)) {
+            entry.aliases.push(alias.to_string());
+        }
+        self
+    }
+
+    /// All inputs of the table
+    pub fn entries(&self) -> &[InputEntry] {
+        &self.entries
+    }
+
+    /// The input with the given name or alias
+    pub fn find(&self, name: &str) -> Option<&InputEntry> {
+        self.entries.iter().find(|entry| entry.matches(name))
+    }
+
+    /// The Input Select value of the input with the given name or alias
+    pub fn value(&self, name: &str) -> Option<u8> {
+        self.find(name).map(|entry| entry.value)
+    }
+
+    /// The name of the input selected by the given value
+    pub fn name(&self, value: u8) -> Option<&str> {
+        self.entries
+            .iter()
+            .rev()
+            .find(|entry| entry.value == value)
+            .map(|entry| entry.name.as_str())
+    }
+}
+
+impl InputEntry {
+    fn matches(&self, name: &str) -> bool {
+        let name = normalize(name);
+        std::iter::once(&self.name)
+            .chain(&self.aliases)
+            .any(|candidate| normalize(candidate) == name)
+    }
+}
+
+/// Lowercases an input name, removes separators and numbers the input 1 if it has no number
+fn normalize(name: &str) -> String {
+    let mut normalized: String = name
+        .chars()
+        .filter(|c| !matches!(c, ' ' | '-' | '_'))
+        .flat_map(char::to_lowercase)
+        .collect();
+    if !normalized.ends_with(|c: char| c.is_ascii_digit()) {
+        normalized.push('1');
+    }
+    normalized
+}
@@ -8,6 +8,7 @@
 //! extern crate ddc;
 //! extern crate ddc_macos;
 //!
+//! # #[cfg(target_os = "macos")]
 //! # fn main() {
 //! use ddc::Ddc;
 //! use ddc_macos::Monitor;
@@ -17,13 +18,52 @@
 //!     println!("Current input: {:04x}", input.value());
 //! }
 //! # }
+//! # #[cfg(not(target_os = "macos"))]
+//! # fn main() {}
 //! ```
+//!
+//! Monitors can also be driven over any [DdcTransport], which allows using the same DDC/CI logic
+//! on platforms without IOKit.
+//!
+//! With the `serde` feature, error kinds, VCP values, monitor identities and information, parsed EDID and
+//! capabilities implement `Serialize` and `Deserialize`.
 
+#[cfg(target_os = "macos")]
 mod arm;
+mod cache;
+mod capabilities;
+pub mod codec;
+pub mod edid;
 mod error;
+mod filter;
+mod identity;
+mod info;
+mod input;
+#[cfg(target_os = "macos")]
 mod intel;
+#[cfg(target_os = "macos")]
 mod iokit;
+mod lut;
 mod monitor;
+mod retry;
+mod settings;
+mod timing;
+mod transition;
+pub mod transport;
+mod vcp;
 
+pub use cache::*;
+pub use capabilities::*;
 pub use error::*;
+pub use filter::*;
+pub use identity::*;
+pub use info::*;
+pub use input::*;
+pub use lut::*;
 pub use monitor::*;
+pub use retry::*;
+pub use settings::*;
+pub use timing::*;
+pub use transition::*;
+pub use transport::DdcTransport;
+pub use vcp::*;
@@ -0,0 +1,220 @@
+use crate::capabilities::Capabilities;
+use crate::error::Error;
+use crate::vcp::VcpFeature;
+use ddc::ErrorCode;
+
+/// A color channel of the lookup tables of a monitor
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum LutChannel {
+    /// Red
+    Red,
+    /// Green
+    Green,
+    /// Blue
+    Blue,
+}
+
+impl LutChannel {
+    /// All channels, in the order MCCS lists them
+    pub const ALL: [LutChannel; 3] = [LutChannel::Red, LutChannel::Green, LutChannel::Blue];
+
+    /// Number of the channel in Block LUT operations: 1 for red, 2 for green and 3 for blue
+    pub fn number(self) -> u8 {
+        self.index() as u8 + 1
+    }
+
+    /// The channel with the given Block LUT operation number
+    pub fn from_number(number: u8) -> Option<Self> {
+        LutChannel::ALL.get((number as usize).checked_sub(1)?).copied()
+    }
+
+    fn index(self) -> usize {
+        match self {
+            LutChannel::Red => 0,
+            LutChannel::Green => 1,
+            LutChannel::Blue => 2,
+        }
+    }
+}
+
+/// How color lookup tables are transferred to and from a monitor
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum LutMode {
+    /// A channel at a time, with Block LUT operations (VCP 0x75)
+    Block,
+    /// An entry of all channels at a time, with Single Point LUT operations (VCP 0x74)
+    SinglePoint,
+}
+
+impl LutMode {
+    /// Block LUT operations if the capabilities list them, Single Point LUT operations otherwise
+    pub fn from_capabilities(capabilities: &Capabilities) -> Self {
+        if capabilities.supports_vcp(VcpFeature::BlockLut.code()) {
+            LutMode::Block
+        } else {
+            LutMode::SinglePoint
+        }
+    }
+}
+
+/// Number of entries and bits per entry of each color lookup table, as reported by LUT Size (VCP 0x73)
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct LutSize {
+    entries: [u16; 3],
+    bits: [u8; 3],
+}
+
+impl LutSize {
+    /// Length of a LUT Size reply: the entries of each channel on two bytes, then the bits of each channel
+    pub const LEN: usize = 9;
+
+    /// A size with the given number of entries and bits per entry for the red, green and blue channels. Tables need
+    /// at least two entries, and entries are 1 to 16 bits.
+    pub fn new(entries: [u16; 3], bits: [u8; 3]) -> Result<Self, Error> {
+        if entries.iter().any(|&entries| entries < 2) || bits.iter().any(|&bits| !(1..=16).contains(&bits)) {
+            return Err(
+                ErrorCode::Invalid(format!("invalid LUT size: {:?} entries of {:?} bits", entries, bits)).into(),
+            );
+        }
+        Ok(LutSize { entries, bits })
+    }
+
+    /// A size with the same number of entries and bits per entry for all channels
+    pub fn uniform(entries: u16, bits: u8) -> Result<Self, Error> {
+        Self::new([entries; 3], [bits; 3])
+    }
+
+    /// Parses a LUT Size reply
+    pub fn parse(data: &[u8]) -> Result<Self, Error> {
+        if data.len() < Self::LEN {
+            return Err(ErrorCode::InvalidLength.into());
+        }
+        let entries = |channel: usize| u16::from_be_bytes([data[channel * 2], data[channel * 2 + 1]]);
+        Self::new([entries(0), entries(1), entries(2)], [data[6], data[7], data[8]])
+    }
+
+    /// Encodes the size as a LUT Size reply
+    pub fn to_bytes(&self) -> [u8; Self::LEN] {
+        let mut data = [0u8; Self::LEN];
+        for (channel, entries) in self.entries.iter().enumerate() {
+            data[channel * 2..channel * 2 + 2].copy_from_slice(&entries.to_be_bytes());
+        }
+        data[6..].copy_from_slice(&self.bits);
+        data
+    }
+
+    /// Number of entries of a channel
+    pub fn entries(&self, channel: LutChannel) -> u16 {
+        self.entries[channel.index()]
+    }
+
+    /// Bits per entry of a channel
+    pub fn bits(&self, channel: LutChannel) -> u8 {
+        self.bits[channel.index()]
+    }
+
+    /// Largest value of an entry of a channel
+    pub fn maximum(&self, channel: LutChannel) -> u16 {
+        (u32::MAX >> (32 - self.bits(channel) as u32)) as u16
+    }
+}
+
+/// Color lookup tables, such as gamma curves, with one table per channel.
+///
+/// Entries are evenly spaced over the input range of the monitor, and hold integer values of the bit depth of their
+/// channel. Tables can be built from any function or sampled curve, and resampled to the size a monitor supports:
+/// values in between entries are linearly interpolated.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct GammaLut {
+    size: LutSize,
+    tables: [Vec<u16>; 3],
+}
+
+impl GammaLut {
+    /// Tables built from a function mapping each channel and normalized input, between 0 and 1, to a normalized
+    /// output. Outputs are clamped to the 0 to 1 range, then rounded to the bit depth of the channel.
+    pub fn from_fn(size: LutSize, mut f: impl FnMut(LutChannel, f64) -> f64) -> Self {
+        let tables = LutChannel::ALL.map(|channel| {
+            let entries = size.entries(channel);
+            let maximum = size.maximum(channel) as f64;
+            (0..entries)
+                .map(|entry| {
+                    let input = entry as f64 / (entries - 1) as f64;
+                    (f(channel, input).clamp(0.0, 1.0) * maximum).round() as u16
+                })
+                .collect()
+        });
+        GammaLut { size, tables }
+    }
+
+    /// Tables that leave colors unchanged
+    pub fn identity(size: LutSize) -> Self {
+        Self::from_fn(size, |_, input| input)
+    }
+
+    /// Tables raising each normalized input to `exponent`, the same for all channels
+    pub fn power(size: LutSize, exponent: f64) -> Self {
+        Self::from_fn(size, |_, input| input.powf(exponent))
+    }
+
+    /// Tables interpolated from curves of normalized outputs, sampled at evenly spaced inputs from 0 to 1. Curves can
+    /// have any number of samples, two at least.
+    pub fn from_curves(size: LutSize, red: &[f64], green: &[f64], blue: &[f64]) -> Result<Self, Error> {
+        let curves = [red, green, blue];
+        if curves.iter().any(|curve| curve.len() < 2) {
+            return Err(ErrorCode::Invalid("LUT curves need at least two samples".to_string()).into());
+        }
+        Ok(Self::from_fn(size, |channel, input| {
+            let curve = curves[channel.index()];
+            interpolate(curve.len(), |index| curve[index], input)
+        }))
+    }
+
+    /// Tables with the given values, which must match the size
+    pub fn from_values(size: LutSize, tables: [Vec<u16>; 3]) -> Result<Self, Error> {
+        for channel in LutChannel::ALL {
+            let table = &tables[channel.index()];
+            if table.len() != size.entries(channel) as usize {
+                return Err(ErrorCode::InvalidLength.into());
+            }
+            if table.iter().any(|&value| value > size.maximum(channel)) {
+                return Err(ErrorCode::InvalidData.into());
+            }
+        }
+        Ok(GammaLut { size, tables })
+    }
+
+    /// Size of the tables
+    pub fn size(&self) -> LutSize {
+        self.size
+    }
+
+    /// Table of a channel
+    pub fn channel(&self, channel: LutChannel) -> &[u16] {
+        &self.tables[channel.index()]
+    }
+
+    /// Normalized output of a channel for a normalized input, interpolated between entries
+    pub fn evaluate(&self, channel: LutChannel, input: f64) -> f64 {
+        let maximum = self.size.maximum(channel) as f64;
+        let table = self.channel(channel);
+        interpolate(table.len(), |index| table[index] as f64 / maximum, input)
+    }
+
+    /// The same curves with another number of entries and bit depth. Resampling to the current size returns
+    /// the tables unchanged.
+    pub fn resample(&self, size: LutSize) -> Self {
+        if size == self.size {
+            return self.clone();
+        }
+        Self::from_fn(size, |channel, input| self.evaluate(channel, input))
+    }
+}
+
+/// Linear interpolation between `len` samples evenly spaced from 0 to 1, `len` being two at least
+fn interpolate(len: usize, sample: impl Fn(usize) -> f64, input: f64) -> f64 {
+    let position = input.clamp(0.0, 1.0) * (len - 1) as f64;
+    let index = (position as usize).min(len - 2);
+    let fraction = position - index as f64;
+    sample(index) * (1.0 - fraction) + sample(index + 1) * fraction
+}
@@ -1,35 +1,96 @@
 #![deny(missing_docs)]
 
+use crate::cache::{CacheKey, CapabilitiesCache};
+use crate::capabilities::Capabilities;
+use crate::codec::{Decoder, Encoder, MAX_DATA_LEN, MAX_FRAGMENT_LEN, PACKET_OVERHEAD};
+use crate::edid::Edid;
 use crate::error::Error;
+use crate::filter::MonitorFilter;
+use crate::identity::MonitorId;
+use crate::info::{Backend, EdidSummary, MonitorInfo};
+use crate::input::InputTable;
+#[cfg(target_os = "macos")]
 use crate::iokit::CoreDisplay_DisplayCreateInfoDictionary;
+#[cfg(target_os = "macos")]
 use crate::iokit::IoObject;
+use crate::lut::{GammaLut, LutChannel, LutMode, LutSize};
+use crate::retry::{RetryPolicy, RetryStats};
+use crate::settings::{FactoryReset, RestoredFeature};
+use crate::timing::TimingReport;
+use crate::transition::{Progress, Transition, TransitionReport};
+use crate::transport::DdcTransport;
+use crate::vcp::{FeatureValue, InputSource, PowerMode, VcpFeature};
+#[cfg(target_os = "macos")]
 use crate::{arm, intel};
+#[cfg(target_os = "macos")]
 use core_foundation::base::{CFType, TCFType};
+#[cfg(target_os = "macos")]
 use core_foundation::data::CFData;
+#[cfg(target_os = "macos")]
 use core_foundation::dictionary::CFDictionary;
+#[cfg(target_os = "macos")]
 use core_foundation::string::{CFString, CFStringRef};
+#[cfg(target_os = "macos")]
 use core_graphics::display::CGDisplay;
+#[cfg(target_os = "macos")]
+use ddc::I2C_ADDRESS_DDC_CI;
 use ddc::{
-    DdcCommand, DdcCommandMarker, DdcCommandRaw, DdcCommandRawMarker, DdcHost, Delay, ErrorCode, I2C_ADDRESS_DDC_CI,
-    SUB_ADDRESS_DDC_CI,
+    Ddc, DdcCommandMarker, DdcCommandRaw, DdcCommandRawMarker, DdcHost, Delay, ErrorCode, FeatureCode, VcpValue,
+    DELAY_COMMAND_FAILED_MS,
 };
+use std::fmt;
 use std::time::Duration;
-use std::{fmt, iter};
+
+/// Time the monitor needs to prepare a Table Read reply
+const TABLE_READ_RESPONSE_DELAY: Duration = Duration::from_millis(40);
+
+/// Time the monitor needs after a table command before the next command
+const TABLE_COMMAND_DELAY: Duration = Duration::from_millis(50);
 
 /// DDC access method for a monitor
+#[cfg(target_os = "macos")]
 #[derive(Debug)]
 enum MonitorService {
     Intel(IoObject),
     Arm(arm::IOAVService),
 }
 
+// SAFETY: IOAVService is a CoreFoundation object, which can be used from any thread. A service is only used by the
+// monitor owning it, through `&mut self`, so it is never used by two threads at once.
+#[cfg(target_os = "macos")]
+unsafe impl Send for MonitorService {}
+
+#[cfg(target_os = "macos")]
+impl DdcTransport for MonitorService {
+    fn execute<'a>(
+        &mut self,
+        i2c_address: u16,
+        packet: &[u8],
+        out: &'a mut [u8],
+        response_delay: Duration,
+    ) -> Result<&'a mut [u8], Error> {
+        match self {
+            MonitorService::Intel(service) => intel::execute(service, i2c_address, packet, out, response_delay),
+            MonitorService::Arm(service) => arm::execute(service, i2c_address, packet, out, response_delay),
+        }
+    }
+}
+
 /// A handle to an attached monitor that allows the use of DDC/CI operations.
 #[derive(Debug)]
 pub struct Monitor {
-    monitor: CGDisplay,
-    service: MonitorService,
+    #[cfg(target_os = "macos")]
+    monitor: Option<CGDisplay>,
+    transport: Box<dyn DdcTransport>,
+    backend: Backend,
     i2c_address: u16,
     delay: Delay,
+    retry_policy: RetryPolicy,
+    retry_stats: RetryStats,
+    edid: Option<Vec<u8>>,
+    capabilities: Option<Capabilities>,
+    capabilities_cache: Option<CapabilitiesCache>,
+    info: MonitorInfo,
 }
 
 impl fmt::Display for Monitor {
@@ -40,16 +101,471 @@ impl fmt::Display for Monitor {
 
 impl Monitor {
     /// Create a new monitor from the specified handle.
+    #[cfg(target_os = "macos")]
     fn new(monitor: CGDisplay, service: MonitorService, i2c_address: u16) -> Self {
-        Monitor {
-            monitor,
-            service,
+        let backend = match service {
+            MonitorService::Intel(_) => Backend::Intel,
+            MonitorService::Arm(_) => Backend::Arm,
+        };
+        let mut monitor = Monitor {
+            monitor: Some(monitor),
+            transport: Box::new(service),
+            backend,
             i2c_address,
             delay: Default::default(),
+            retry_policy: RetryPolicy::none(),
+            retry_stats: Default::default(),
+            edid: None,
+            capabilities: None,
+            capabilities_cache: CapabilitiesCache::user_default(),
+            info: Default::default(),
+        };
+        monitor.info = monitor.gather_info();
+        monitor
+    }
+
+    /// Create a monitor that talks DDC/CI over the given transport, using the device at `i2c_address`.
+    ///
+    /// All [ddc::Ddc] operations work on such a monitor, which is not backed by any display known to
+    /// CoreGraphics.
+    pub fn with_transport<T: DdcTransport + 'static>(transport: T, i2c_address: u16) -> Self {
+        let mut monitor = Monitor {
+            #[cfg(target_os = "macos")]
+            monitor: None,
+            transport: Box::new(transport),
+            backend: Backend::Custom,
+            i2c_address,
+            delay: Default::default(),
+            retry_policy: RetryPolicy::none(),
+            retry_stats: Default::default(),
+            edid: None,
+            capabilities: None,
+            capabilities_cache: None,
+            info: Default::default(),
+        };
+        monitor.info = monitor.gather_info();
+        monitor
+    }
+
+    /// Replace the transport of this monitor with one built around the current transport, for example to wrap it
+    /// in a [FaultInjector](crate::transport::FaultInjector).
+    pub fn map_transport<T, F>(self, f: F) -> Self
+    where
+        T: DdcTransport + 'static,
+        F: FnOnce(Box<dyn DdcTransport>) -> T,
+    {
+        Monitor {
+            transport: Box::new(f(self.transport)),
+            ..self
+        }
+    }
+
+    /// Returns the transport of this monitor, for example to [flush](DdcTransport::flush) a
+    /// [Recorder](crate::transport::Recorder) once done with the monitor.
+    pub fn into_transport(self) -> Box<dyn DdcTransport> {
+        self.transport
+    }
+
+    /// Set how failed DDC/CI commands are retried. By default, commands are not retried.
+    pub fn set_retry_policy(&mut self, retry_policy: RetryPolicy) {
+        self.retry_policy = retry_policy;
+    }
+
+    /// How failed DDC/CI commands are retried
+    pub fn retry_policy(&self) -> &RetryPolicy {
+        &self.retry_policy
+    }
+
+    /// Statistics of the commands executed and retried since this monitor was created or the statistics reset
+    pub fn retry_stats(&self) -> RetryStats {
+        self.retry_stats
+    }
+
+    /// Reset the retry statistics
+    pub fn reset_retry_stats(&mut self) {
+        self.retry_stats = Default::default();
+    }
+
+    /// Capabilities of this monitor.
+    ///
+    /// The capabilities string is only read from the monitor the first time, then kept in memory. If the monitor has
+    /// a capabilities cache and EDID, the string is also stored in the cache, and read from there next time instead
+    /// of from the monitor. Capabilities without a model nor VCP features, as read from a monitor that did not answer
+    /// properly, are neither kept nor cached.
+    pub fn capabilities(&mut self) -> Result<Capabilities, Error> {
+        if let Some(capabilities) = &self.capabilities {
+            return Ok(capabilities.clone());
+        }
+        let cache = self.capabilities_cache_entry();
+        let cached = cache
+            .as_ref()
+            .and_then(|(cache, key)| cache.load(key))
+            .map(|data| Capabilities::parse(&data))
+            .filter(is_complete);
+        if let Some(capabilities) = cached {
+            self.capabilities = Some(capabilities.clone());
+            return Ok(capabilities);
+        }
+        let data = self.capabilities_string()?;
+        let capabilities = Capabilities::parse(&data);
+        // An empty or truncated reply would be reused forever: it is returned, but neither kept nor cached
+        if is_complete(&capabilities) {
+            if let Some((cache, key)) = &cache {
+                // The cache only saves time: failing to write it is not an error
+                let _ = cache.store(key, &data);
+            }
+            self.capabilities = Some(capabilities.clone());
+        }
+        Ok(capabilities)
+    }
+
+    /// Forget the capabilities of this monitor, in memory and in the capabilities cache, so that they are read from
+    /// the monitor again, for example after a firmware update.
+    pub fn invalidate_capabilities(&mut self) -> Result<(), Error> {
+        self.capabilities = None;
+        if let Some((cache, key)) = self.capabilities_cache_entry() {
+            cache.invalidate(&key)?;
+        }
+        Ok(())
+    }
+
+    /// Set the cache of capabilities strings used by [Monitor::capabilities], or disable it with `None`. Enumerated
+    /// monitors use [CapabilitiesCache::user_default], other monitors have no cache by default.
+    pub fn set_capabilities_cache(&mut self, cache: Option<CapabilitiesCache>) {
+        self.capabilities_cache = cache;
+    }
+
+    /// The cache of capabilities strings, if any
+    pub fn capabilities_cache(&self) -> Option<&CapabilitiesCache> {
+        self.capabilities_cache.as_ref()
+    }
+
+    /// The capabilities cache with the key of this monitor, if both are known
+    fn capabilities_cache_entry(&self) -> Option<(CapabilitiesCache, CacheKey)> {
+        let cache = self.capabilities_cache.clone()?;
+        let key = CacheKey::from_edid(&self.edid()?)?;
+        Some((cache, key))
+    }
+
+    /// Reads a VCP feature. Features not listed in [VcpFeature] can be read by code with [Ddc::get_vcp_feature].
+    pub fn get_feature(&mut self, feature: VcpFeature) -> Result<VcpValue, Error> {
+        self.get_vcp_feature(feature.code())
+    }
+
+    /// Sets a VCP feature. Features not listed in [VcpFeature] can be set by code with [Ddc::set_vcp_feature].
+    pub fn set_feature(&mut self, feature: VcpFeature, value: u16) -> Result<(), Error> {
+        self.set_vcp_feature(feature.code(), value)
+    }
+
+    /// Reads the typed value of a non-continuous VCP feature, e.g. `monitor.get_value::<ColorPreset>()`
+    pub fn get_value<T: FeatureValue>(&mut self) -> Result<T, Error> {
+        Ok(T::from_vcp(self.get_feature(T::FEATURE)?.value()))
+    }
+
+    /// Sets the typed value of a non-continuous VCP feature. Values without an MCCS encoding are rejected without
+    /// sending anything to the monitor.
+    pub fn set_value<T: FeatureValue>(&mut self, value: T) -> Result<(), Error> {
+        let value = value.to_vcp()?;
+        self.set_feature(T::FEATURE, value)
+    }
+
+    /// The video input currently displayed
+    pub fn input_source(&mut self) -> Result<InputSource, Error> {
+        self.get_value()
+    }
+
+    /// Switch to another video input
+    pub fn set_input_source(&mut self, input: InputSource) -> Result<(), Error> {
+        self.set_value(input)
+    }
+
+    /// Inputs of this monitor: the MCCS standard inputs, with the vendor overrides matching its EDID
+    pub fn input_table(&self) -> InputTable {
+        self.edid().map(|edid| InputTable::for_edid(&edid)).unwrap_or_default()
+    }
+
+    /// Switch to the input with the given name, such as `usb-c`, `dp1` or `HDMI 2`, looked up in
+    /// [Monitor::input_table]
+    pub fn select_input(&mut self, name: &str) -> Result<(), Error> {
+        let value = self
+            .input_table()
+            .value(name)
+            .ok_or_else(|| ErrorCode::Invalid(format!("unknown input: {}", name)))?;
+        self.set_feature(VcpFeature::InputSource, value as u16)
+    }
+
+    /// Name of the video input currently displayed, looked up in [Monitor::input_table]
+    pub fn input_name(&mut self) -> Result<String, Error> {
+        let value = self.get_feature(VcpFeature::InputSource)?.value() as u8;
+        Ok(match self.input_table().name(value) {
+            Some(name) => name.to_string(),
+            None => InputSource::from_value(value).name(),
+        })
+    }
+
+    /// The power state of the display
+    pub fn power_mode(&mut self) -> Result<PowerMode, Error> {
+        self.get_value()
+    }
+
+    /// Change the power state of the display
+    pub fn set_power_mode(&mut self, mode: PowerMode) -> Result<(), Error> {
+        self.set_value(mode)
+    }
+
+    /// Frequencies and sync status of the video signal the monitor receives
+    pub fn timing_report(&mut self) -> Result<TimingReport, Error> {
+        Ok(self.get_timing_report()?.into())
+    }
+
+    /// Animate a continuous VCP feature from its current value to the target of `transition`, returning once the
+    /// target is reached or the transition is cancelled. Use [run_transitions](crate::run_transitions) to animate several monitors at once.
+    pub fn transition(&mut self, transition: Transition) -> Result<TransitionReport, Error> {
+        Progress::run(self, &transition)
+    }
+
+    /// Save the current settings in the non-volatile memory of the monitor, so that they survive a power cycle.
+    /// Most monitors save changes on their own after a while; this makes sure they are saved now. Returns after the
+    /// 200 ms the monitor needs before the next command.
+    pub fn save_settings(&mut self) -> Result<(), Error> {
+        self.sleep();
+        self.save_current_settings()?;
+        self.sleep();
+        Ok(())
+    }
+
+    /// Restore factory settings, and verify the reset by reading back the affected features the monitor supports.
+    ///
+    /// Returns after the time the monitor needs to restore its settings, with the values of the affected features
+    /// before and after the reset. Failing to read back a feature that could be read before the reset is an error,
+    /// as the monitor is not responding properly.
+    pub fn restore_factory(&mut self, reset: FactoryReset) -> Result<Vec<RestoredFeature>, Error> {
+        let mut before = Vec::new();
+        for &feature in reset.affected_features() {
+            self.sleep();
+            // Features the monitor does not support cannot be verified
+            if let Ok(value) = self.get_feature(feature) {
+                before.push((feature, value.value()));
+            }
+        }
+        self.sleep();
+        self.set_feature(reset.feature(), 1)?;
+        self.delay = Delay::new(reset.delay());
+        self.sleep();
+        before
+            .into_iter()
+            .map(|(feature, before)| {
+                self.sleep();
+                let after = self.get_feature(feature)?.value();
+                Ok(RestoredFeature { feature, before, after })
+            })
+            .collect()
+    }
+
+    /// Reads a whole table feature, such as a gamma LUT, fragment by fragment until the monitor replies with an empty
+    /// fragment.
+    ///
+    /// Each fragment is retried on its own according to the retry policy, without restarting the whole table. Besides
+    /// the length and checksum of each reply, the offset of each fragment is checked against the one requested.
+    pub fn read_table(&mut self, code: impl Into<FeatureCode>) -> Result<Vec<u8>, Error> {
+        let code = code.into();
+        let mut table = Vec::new();
+        loop {
+            let offset = u16::try_from(table.len()).map_err(|_| ErrorCode::InvalidOffset)?;
+            let mut fragment = [0u8; MAX_FRAGMENT_LEN];
+            let len = self.table_command(|monitor| monitor.read_table_fragment(code, offset, &mut fragment))?;
+            if len == 0 {
+                break;
+            }
+            table.extend_from_slice(&fragment[..len]);
+        }
+        Ok(table)
+    }
+
+    /// Writes `data` into a table feature starting at `offset`, in fragments of up to 32 bytes, each retried on its
+    /// own according to the retry policy.
+    pub fn write_table(&mut self, code: impl Into<FeatureCode>, offset: u16, data: &[u8]) -> Result<(), Error> {
+        let code = code.into();
+        if offset as usize + data.len() > u16::MAX as usize + 1 {
+            return Err(ErrorCode::Invalid(format!(
+                "table data too long: {} bytes at offset {}",
+                data.len(),
+                offset
+            ))
+            .into());
+        }
+        for (index, chunk) in data.chunks(MAX_FRAGMENT_LEN).enumerate() {
+            let fragment_offset = offset + (index * MAX_FRAGMENT_LEN) as u16;
+            let mut request = vec![0xe7, code];
+            request.extend_from_slice(&fragment_offset.to_be_bytes());
+            request.extend_from_slice(chunk);
+            self.table_command(|monitor| {
+                monitor.with_retries(|monitor| monitor.exchange(&request, &mut [], Duration::ZERO))
+            })?;
+        }
+        Ok(())
+    }
+
+    /// Size of the color lookup tables of the monitor, read from LUT Size (VCP 0x73)
+    pub fn lut_size(&mut self) -> Result<LutSize, Error> {
+        LutSize::parse(&self.read_table(VcpFeature::LutSize)?)
+    }
+
+    /// How the monitor transfers color lookup tables, according to its [capabilities](Monitor::capabilities), which
+    /// are read from the monitor if they are not known yet
+    pub fn lut_mode(&mut self) -> Result<LutMode, Error> {
+        Ok(LutMode::from_capabilities(&self.capabilities()?))
+    }
+
+    /// Load color lookup tables into the monitor with the operations of `mode`, resampled to the size it reports
+    /// first. See [Monitor::lut_mode] for the mode the monitor supports.
+    pub fn upload_lut(&mut self, lut: &GammaLut, mode: LutMode) -> Result<(), Error> {
+        let lut = lut.resample(self.lut_size()?);
+        if mode == LutMode::Block {
+            for channel in LutChannel::ALL {
+                let mut block = vec![channel.number(), 0, 0];
+                block.extend(lut.channel(channel).iter().flat_map(|value| value.to_be_bytes()));
+                self.write_table(VcpFeature::BlockLut, 0, &block)?;
+            }
+        } else {
+            let entries = LutChannel::ALL
+                .iter()
+                .map(|&channel| lut.size().entries(channel))
+                .max()
+                .unwrap_or(0);
+            for entry in 0..entries {
+                let mut point = entry.to_be_bytes().to_vec();
+                for channel in LutChannel::ALL {
+                    // Channels with fewer entries repeat their last one
+                    let table = lut.channel(channel);
+                    point.extend_from_slice(&table[(entry as usize).min(table.len() - 1)].to_be_bytes());
+                }
+                self.write_table(VcpFeature::SinglePointLut, 0, &point)?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Read the color lookup tables of the monitor with the operations of `mode`
+    pub fn read_lut(&mut self, mode: LutMode) -> Result<GammaLut, Error> {
+        let size = self.lut_size()?;
+        let mut tables: [Vec<u16>; 3] = Default::default();
+        if mode == LutMode::Block {
+            for (index, channel) in LutChannel::ALL.into_iter().enumerate() {
+                self.write_table(VcpFeature::BlockLut, 0, &[channel.number(), 0, 0])?;
+                let block = self.read_table(VcpFeature::BlockLut)?;
+                tables[index] = block
+                    .chunks_exact(2)
+                    .map(|value| u16::from_be_bytes([value[0], value[1]]))
+                    .take(size.entries(channel) as usize)
+                    .collect();
+            }
+        } else {
+            let entries = LutChannel::ALL
+                .iter()
+                .map(|&channel| size.entries(channel))
+                .max()
+                .unwrap_or(0);
+            for entry in 0..entries {
+                self.write_table(VcpFeature::SinglePointLut, 0, &entry.to_be_bytes())?;
+                let point = self.read_table(VcpFeature::SinglePointLut)?;
+                if point.len() < 8 {
+                    return Err(ErrorCode::InvalidLength.into());
+                }
+                if point[..2] != entry.to_be_bytes() {
+                    return Err(ErrorCode::InvalidOffset.into());
+                }
+                for (index, channel) in LutChannel::ALL.into_iter().enumerate() {
+                    if entry < size.entries(channel) {
+                        tables[index].push(u16::from_be_bytes([point[2 + index * 2], point[3 + index * 2]]));
+                    }
+                }
+            }
+        }
+        GammaLut::from_values(size, tables)
+    }
+
+    /// Read the color lookup tables back, and check that they match `lut` resampled to the size of the monitor
+    pub fn verify_lut(&mut self, lut: &GammaLut, mode: LutMode) -> Result<bool, Error> {
+        let loaded = self.read_lut(mode)?;
+        Ok(loaded == lut.resample(loaded.size()))
+    }
+
+    /// Requests one table fragment, copying its data to `out` and returning its length. Replies for another offset
+    /// are retried like framing errors, as they are usually stale replies to the previous fragment.
+    fn read_table_fragment(&mut self, code: FeatureCode, offset: u16, out: &mut [u8]) -> Result<usize, Error> {
+        let [offset_high, offset_low] = offset.to_be_bytes();
+        let request = [0xe2, code, offset_high, offset_low];
+        self.with_retries(|monitor| {
+            let mut reply = [0u8; MAX_DATA_LEN + PACKET_OVERHEAD];
+            let len = monitor.exchange(&request, &mut reply, TABLE_READ_RESPONSE_DELAY)?;
+            let reply = &reply[..len];
+            if reply.len() < 3 || reply.len() > 3 + MAX_FRAGMENT_LEN {
+                return Err(ErrorCode::InvalidLength.into());
+            }
+            if reply[1..3] != request[2..4] {
+                return Err(ErrorCode::InvalidOffset.into());
+            }
+            out[..len - 3].copy_from_slice(&reply[3..]);
+            Ok(len - 3)
+        })
+    }
+
+    /// Waits for the previous command to complete, runs a table command and sets the delay before the next one, like
+    /// [ddc] does for the commands it sends.
+    fn table_command<R>(&mut self, command: impl FnOnce(&mut Self) -> Result<R, Error>) -> Result<R, Error> {
+        self.sleep();
+        let result = command(self);
+        let delay = match result {
+            Ok(_) => TABLE_COMMAND_DELAY,
+            Err(_) => Duration::from_millis(DELAY_COMMAND_FAILED_MS),
+        };
+        self.delay = Delay::new(delay);
+        result
+    }
+
+    /// Runs `attempt` until it succeeds, retrying failures according to the retry policy and counting them in the
+    /// retry statistics.
+    fn with_retries<R>(&mut self, mut attempt: impl FnMut(&mut Self) -> Result<R, Error>) -> Result<R, Error> {
+        self.retry_stats.commands += 1;
+        let mut number = 1;
+        loop {
+            match attempt(self) {
+                Ok(result) => {
+                    if number > 1 {
+                        self.retry_stats.recovered += 1;
+                    }
+                    return Ok(result);
+                }
+                Err(error) if self.retry_policy.should_retry(&error, number) => {
+                    self.retry_stats.retries += 1;
+                    std::thread::sleep(self.retry_policy.backoff(number));
+                    number += 1;
+                }
+                Err(error) => {
+                    self.retry_stats.failed += 1;
+                    return Err(error);
+                }
+            }
         }
     }
 
+    /// Sends a command once, copying the decoded reply data to `out` and returning its length.
+    fn exchange(&mut self, data: &[u8], out: &mut [u8], response_delay: Duration) -> Result<usize, Error> {
+        let mut packet = [0u8; MAX_DATA_LEN + PACKET_OVERHEAD];
+        let packet = Encoder::new(self.i2c_address).encode(data, &mut packet);
+        // The reply is read into a scratch buffer so that `out` is left alone by failed attempts
+        let mut reply = vec![0u8; out.len()];
+        let response = self
+            .transport
+            .execute(self.i2c_address, packet, &mut reply, response_delay)?;
+        let decoded = Decoder::new(self.i2c_address).decode_reply(data, response)?;
+        out[..decoded.len()].copy_from_slice(decoded);
+        Ok(decoded.len())
+    }
+
     /// Enumerate all connected physical monitors returning [Vec<Monitor>]
+    #[cfg(target_os = "macos")]
     pub fn enumerate() -> Result<Vec<Self>, Error> {
         let monitors = CGDisplay::active_displays()
             .map_err(Error::from)?
@@ -71,16 +587,124 @@ impl Monitor {
     /// Physical monitor description string. If it cannot get the product's name it will use
     /// the vendor number and model number to form a description
     pub fn description(&self) -> String {
-        self.product_name().unwrap_or(format!(
-            "{:04x}:{:04x}",
-            self.monitor.vendor_number(),
-            self.monitor.model_number()
-        ))
+        #[cfg(target_os = "macos")]
+        if let Some(monitor) = self.monitor {
+            return self.product_name().unwrap_or(format!(
+                "{:04x}:{:04x}",
+                monitor.vendor_number(),
+                monitor.model_number()
+            ));
+        }
+        format!("DDC/CI device at {:#04x}", self.i2c_address)
+    }
+
+    /// Find the monitor with the given identity among the connected monitors, `None` if it is not connected or cannot
+    /// be told apart from another one. See [MonitorId::position_in].
+    #[cfg(target_os = "macos")]
+    pub fn find(id: &MonitorId) -> Result<Option<Self>, Error> {
+        Ok(Self::find_in(Self::enumerate()?, id))
+    }
+
+    /// Find the monitor with the given identity among `monitors`, like [Monitor::find]
+    pub fn find_in(monitors: Vec<Self>, id: &MonitorId) -> Option<Self> {
+        // Monitors without EDID have no identity, and cannot be found
+        let (indices, ids): (Vec<usize>, Vec<MonitorId>) = monitors
+            .iter()
+            .enumerate()
+            .filter_map(|(index, monitor)| Some((index, monitor.id()?)))
+            .unzip();
+        let index = indices[id.position_in(&ids)?];
+        monitors.into_iter().nth(index)
+    }
+
+    /// Enumerate the connected monitors selected by `filter`
+    #[cfg(target_os = "macos")]
+    pub fn enumerate_matching(filter: &MonitorFilter) -> Result<Vec<Self>, Error> {
+        Ok(Self::filter_in(Self::enumerate()?, filter))
+    }
+
+    /// The monitors of `monitors` selected by `filter`, indices being positions in `monitors`
+    pub fn filter_in(monitors: Vec<Self>, filter: &MonitorFilter) -> Vec<Self> {
+        monitors
+            .into_iter()
+            .enumerate()
+            .filter(|(index, monitor)| filter.matches(*index, monitor.info()))
+            .map(|(_, monitor)| monitor)
+            .collect()
+    }
+
+    /// How this monitor is driven
+    pub fn backend(&self) -> Backend {
+        self.backend
+    }
+
+    /// Metadata describing this monitor, from its EDID and CoreDisplay. The snapshot is gathered when the monitor
+    /// is created, and again when its EDID is set with [Monitor::set_edid].
+    pub fn info(&self) -> &MonitorInfo {
+        &self.info
+    }
+
+    fn gather_info(&self) -> MonitorInfo {
+        let raw_edid = self.edid();
+        let edid = raw_edid.as_ref().and_then(|edid| Edid::parse(edid).ok());
+        #[cfg(target_os = "macos")]
+        let (model, serial, location) = (self.product_name(), self.serial_number(), self.display_location());
+        #[cfg(not(target_os = "macos"))]
+        let (model, serial, location) = (None, None, None);
+        #[cfg(target_os = "macos")]
+        let (display_id, builtin) = match self.monitor {
+            Some(monitor) => (Some(monitor.id), monitor.is_builtin()),
+            None => (None, false),
+        };
+        #[cfg(not(target_os = "macos"))]
+        let (display_id, builtin) = (None, false);
+        let edid_serial = edid.as_ref().and_then(|edid| match edid.serial_string() {
+            Some(serial) if !serial.is_empty() => Some(serial.to_string()),
+            _ if edid.serial_number != 0 => Some(edid.serial_number.to_string()),
+            _ => None,
+        });
+        MonitorInfo {
+            backend: self.backend,
+            i2c_address: self.i2c_address,
+            display_id,
+            builtin,
+            vendor: edid.as_ref().map(|edid| edid.manufacturer.clone()),
+            product: edid.as_ref().map(|edid| edid.product_code),
+            model: model.or_else(|| edid.as_ref()?.product_name().map(str::to_string)),
+            serial: edid_serial.or(serial),
+            description: self.description(),
+            id: raw_edid
+                .as_deref()
+                .and_then(|raw_edid| MonitorId::from_edid(raw_edid, location.clone())),
+            location,
+            edid_summary: raw_edid.as_deref().and_then(EdidSummary::parse),
+            edid: raw_edid,
+        }
+    }
+
+    /// Stable identity of this monitor, from its EDID and display location. `None` without EDID.
+    pub fn id(&self) -> Option<MonitorId> {
+        #[cfg(target_os = "macos")]
+        let location = self.display_location();
+        #[cfg(not(target_os = "macos"))]
+        let location = None;
+        MonitorId::from_edid(&self.edid()?, location)
+    }
+
+    /// IORegistry location of the display of this [Monitor], `IODisplayLocation`, if available
+    #[cfg(target_os = "macos")]
+    pub fn display_location(&self) -> Option<String> {
+        let monitor = self.monitor?;
+        let info: CFDictionary<CFString, CFType> =
+            unsafe { CFDictionary::wrap_under_create_rule(CoreDisplay_DisplayCreateInfoDictionary(monitor.id)) };
+        let location = info.find(CFString::from_static_string("IODisplayLocation"))?;
+        Some(location.downcast::<CFString>()?.to_string())
     }
 
     /// Serial number for this [Monitor]
+    #[cfg(target_os = "macos")]
     pub fn serial_number(&self) -> Option<String> {
-        let serial = self.monitor.serial_number();
+        let serial = self.monitor?.serial_number();
         match serial {
             0 => None,
             _ => Some(format!("{}", serial)),
@@ -88,9 +712,11 @@ impl Monitor {
     }
 
     /// Product name for this [Monitor], if available
+    #[cfg(target_os = "macos")]
     pub fn product_name(&self) -> Option<String> {
+        let monitor = self.monitor?;
         let info: CFDictionary<CFString, CFType> =
-            unsafe { CFDictionary::wrap_under_create_rule(CoreDisplay_DisplayCreateInfoDictionary(self.monitor.id)) };
+            unsafe { CFDictionary::wrap_under_create_rule(CoreDisplay_DisplayCreateInfoDictionary(monitor.id)) };
 
         let display_product_name_key = CFString::from_static_string("DisplayProductName");
         let display_product_names_dict = info.find(&display_product_name_key)?.downcast::<CFDictionary>()?;
@@ -100,46 +726,42 @@ impl Monitor {
             .map(|name| unsafe { CFString::wrap_under_get_rule(*name as CFStringRef) }.to_string())
     }
 
-    /// Returns Extended display identification data (EDID) for this [Monitor] as raw bytes data
+    /// Returns Extended display identification data (EDID) for this [Monitor] as raw bytes data: the data set with
+    /// [Monitor::set_edid], or else the data CoreGraphics has for the display
     pub fn edid(&self) -> Option<Vec<u8>> {
-        let info: CFDictionary<CFString, CFType> =
-            unsafe { CFDictionary::wrap_under_create_rule(CoreDisplay_DisplayCreateInfoDictionary(self.monitor.id)) };
-        let display_product_name_key = CFString::from_static_string("IODisplayEDIDOriginal");
-        let edid_data = info.find(&display_product_name_key)?.downcast::<CFData>()?;
-        Some(edid_data.bytes().into())
+        if let Some(edid) = &self.edid {
+            return Some(edid.clone());
+        }
+        #[cfg(target_os = "macos")]
+        {
+            let monitor = self.monitor?;
+            let info: CFDictionary<CFString, CFType> =
+                unsafe { CFDictionary::wrap_under_create_rule(CoreDisplay_DisplayCreateInfoDictionary(monitor.id)) };
+            let display_product_name_key = CFString::from_static_string("IODisplayEDIDOriginal");
+            let edid_data = info.find(&display_product_name_key)?.downcast::<CFData>()?;
+            Some(edid_data.bytes().into())
+        }
+        #[cfg(not(target_os = "macos"))]
+        None
     }
 
-    /// CoreGraphics display handle for this monitor
-    pub fn handle(&self) -> CGDisplay {
-        self.monitor
+    /// Set the EDID of this monitor, for monitors not backed by a display, or to override broken EDID data
+    pub fn set_edid(&mut self, edid: Vec<u8>) {
+        self.edid = Some(edid);
+        self.info = self.gather_info();
     }
 
-    fn encode_command<'a>(&self, data: &[u8], packet: &'a mut [u8]) -> &'a [u8] {
-        packet[0] = SUB_ADDRESS_DDC_CI;
-        packet[1] = 0x80 | data.len() as u8;
-        packet[2..2 + data.len()].copy_from_slice(data);
-        packet[2 + data.len()] =
-            Self::checksum(iter::once((self.i2c_address as u8) << 1).chain(packet[..2 + data.len()].iter().cloned()));
-        &packet[..3 + data.len()]
+    /// CoreGraphics display handle for this monitor. Monitors created with [Monitor::with_transport] are not backed
+    /// by a display, and return [CGDisplay::null_display]: see [Monitor::display].
+    #[cfg(target_os = "macos")]
+    pub fn handle(&self) -> CGDisplay {
+        self.monitor.unwrap_or_else(CGDisplay::null_display)
     }
 
-    fn decode_response<'a>(&self, response: &'a mut [u8]) -> Result<&'a mut [u8], crate::error::Error> {
-        if response.is_empty() {
-            return Ok(response);
-        };
-        let len = (response[1] & 0x7f) as usize;
-        if len + 2 >= response.len() {
-            return Err(Error::Ddc(ErrorCode::InvalidLength));
-        }
-        let checksum = Self::checksum(
-            iter::once(((self.i2c_address << 1) | 1) as u8)
-                .chain(iter::once(SUB_ADDRESS_DDC_CI))
-                .chain(response[1..2 + len].iter().cloned()),
-        );
-        if response[2 + len] != checksum {
-            return Err(Error::Ddc(ErrorCode::InvalidChecksum));
-        }
-        Ok(&mut response[2..2 + len])
+    /// CoreGraphics display handle for this monitor, `None` if it is not backed by a CoreGraphics display
+    #[cfg(target_os = "macos")]
+    pub fn display(&self) -> Option<CGDisplay> {
+        self.monitor
     }
 }
 
@@ -158,14 +780,8 @@ impl DdcCommandRaw for Monitor {
         out: &'a mut [u8],
         response_delay: Duration,
     ) -> Result<&'a mut [u8], Self::Error> {
-        assert!(data.len() <= 36);
-        let mut packet = [0u8; 36 + 3];
-        let packet = self.encode_command(data, &mut packet);
-        let response = match &self.service {
-            MonitorService::Intel(service) => intel::execute(service, self.i2c_address, packet, out, response_delay),
-            MonitorService::Arm(service) => arm::execute(service, self.i2c_address, packet, out, response_delay),
-        }?;
-        self.decode_response(response)
+        let len = self.with_retries(|monitor| monitor.exchange(data, out, response_delay))?;
+        Ok(&mut out[..len])
     }
 }
 
@@ -176,3 +792,8 @@ impl DdcCommandRawMarker for Monitor {
         self.delay = delay;
     }
 }
+
+/// Whether capabilities describe the monitor, rather than coming from an empty or truncated reply
+fn is_complete(capabilities: &Capabilities) -> bool {
+    capabilities.model.is_some() || !capabilities.vcp_features.is_empty()
+}
@@ -0,0 +1,110 @@
+use crate::error::{Error, ErrorKind};
+use ddc::DELAY_COMMAND_FAILED_MS;
+use std::time::Duration;
+
+/// How a [Monitor](crate::Monitor) retries DDC/CI commands that failed.
+///
+/// A command is attempted up to `max_attempts` times. Before retry number `n` (starting at 1) the monitor waits
+/// for the `n`-th delay of the backoff schedule, the last delay of the schedule being used for all further
+/// retries. Only errors of a retryable [ErrorKind] are retried.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RetryPolicy {
+    max_attempts: u32,
+    backoff: Vec<Duration>,
+    retryable: Vec<ErrorKind>,
+}
+
+impl Default for RetryPolicy {
+    /// Three attempts with an exponential backoff starting at the DDC/CI failed command delay, retrying transient
+    /// transport and framing errors.
+    fn default() -> Self {
+        RetryPolicy {
+            max_attempts: 3,
+            backoff: vec![
+                Duration::from_millis(DELAY_COMMAND_FAILED_MS),
+                Duration::from_millis(DELAY_COMMAND_FAILED_MS * 2),
+            ],
+            retryable: vec![
+                ErrorKind::Io,
+                ErrorKind::InvalidOffset,
+                ErrorKind::InvalidLength,
+                ErrorKind::InvalidChecksum,
+                ErrorKind::MonitorBusy,
+                ErrorKind::SourceAddressMismatch,
+                ErrorKind::OpcodeMismatch,
+            ],
+        }
+    }
+}
+
+impl RetryPolicy {
+    /// A policy that never retries: every command is attempted once
+    pub fn none() -> Self {
+        RetryPolicy {
+            max_attempts: 1,
+            ..Default::default()
+        }
+    }
+
+    /// Set the maximum number of attempts, including the first one
+    pub fn with_max_attempts(self, max_attempts: u32) -> Self {
+        RetryPolicy {
+            max_attempts: max_attempts.max(1),
+            ..self
+        }
+    }
+
+    /// Set the delays to wait before consecutive retries
+    pub fn with_backoff(self, backoff: impl IntoIterator<Item = Duration>) -> Self {
+        RetryPolicy {
+            backoff: backoff.into_iter().collect(),
+            ..self
+        }
+    }
+
+    /// Set the kinds of errors that are retried
+    pub fn with_retryable(self, retryable: impl IntoIterator<Item = ErrorKind>) -> Self {
+        RetryPolicy {
+            retryable: retryable.into_iter().collect(),
+            ..self
+        }
+    }
+
+    /// Maximum number of attempts of a command, including the first one
+    pub fn max_attempts(&self) -> u32 {
+        self.max_attempts
+    }
+
+    /// Returns true if errors of this kind are retried
+    pub fn is_retryable(&self, error: &Error) -> bool {
+        self.retryable.contains(&error.kind())
+    }
+
+    /// Delay to wait before the given retry, starting at 1
+    pub fn backoff(&self, retry: u32) -> Duration {
+        let index = (retry.max(1) - 1) as usize;
+        self.backoff
+            .get(index)
+            .or_else(|| self.backoff.last())
+            .copied()
+            .unwrap_or_default()
+    }
+
+    /// Returns true if a command that failed with `error` on the given attempt, starting at 1, should be retried
+    pub(crate) fn should_retry(&self, error: &Error, attempt: u32) -> bool {
+        attempt < self.max_attempts && self.is_retryable(error)
+    }
+}
+
+/// Counters of the retries made by a [Monitor](crate::Monitor)
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct RetryStats {
+    /// Commands executed
+    pub commands: u64,
+    /// Retries made, over all commands
+    pub retries: u64,
+    /// Commands that failed at first and succeeded after retrying
+    pub recovered: u64,
+    /// Commands that failed, after retrying if the error was retryable
+    pub failed: u64,
+}
@@ -0,0 +1,77 @@
+use crate::vcp::VcpFeature;
+use std::time::Duration;
+
+/// Settings restored to their factory values by a factory reset command
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum FactoryReset {
+    /// All settings, VCP 0x04
+    All,
+    /// Luminance and contrast, VCP 0x05
+    LuminanceContrast,
+    /// Geometry, VCP 0x06
+    Geometry,
+    /// Color, VCP 0x08
+    Color,
+}
+
+impl FactoryReset {
+    /// The write-only feature triggering the reset
+    pub fn feature(self) -> VcpFeature {
+        match self {
+            FactoryReset::All => VcpFeature::RestoreFactoryDefaults,
+            FactoryReset::LuminanceContrast => VcpFeature::RestoreFactoryLuminanceContrast,
+            FactoryReset::Geometry => VcpFeature::RestoreFactoryGeometry,
+            FactoryReset::Color => VcpFeature::RestoreFactoryColor,
+        }
+    }
+
+    /// Features affected by the reset that can be read back to verify it. Geometry features are not listed in
+    /// [VcpFeature], so a geometry reset cannot be verified.
+    pub fn affected_features(self) -> &'static [VcpFeature] {
+        match self {
+            FactoryReset::All => &[
+                VcpFeature::Luminance,
+                VcpFeature::Contrast,
+                VcpFeature::ColorPreset,
+                VcpFeature::RedGain,
+                VcpFeature::GreenGain,
+                VcpFeature::BlueGain,
+            ],
+            FactoryReset::LuminanceContrast => &[VcpFeature::Luminance, VcpFeature::Contrast],
+            FactoryReset::Geometry => &[],
+            FactoryReset::Color => &[
+                VcpFeature::ColorPreset,
+                VcpFeature::RedGain,
+                VcpFeature::GreenGain,
+                VcpFeature::BlueGain,
+            ],
+        }
+    }
+
+    /// Time the monitor needs to restore its settings before it answers further commands reliably. MCCS does not
+    /// specify it, and monitors rewrite their non-volatile memory meanwhile, so this errs on the long side.
+    pub fn delay(self) -> Duration {
+        match self {
+            FactoryReset::All => Duration::from_millis(1000),
+            _ => Duration::from_millis(500),
+        }
+    }
+}
+
+/// A feature read back after a factory reset
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RestoredFeature {
+    /// The feature
+    pub feature: VcpFeature,
+    /// Value before the reset
+    pub before: u16,
+    /// Value after the reset
+    pub after: u16,
+}
+
+impl RestoredFeature {
+    /// Whether the reset changed the value
+    pub fn changed(&self) -> bool {
+        self.before != self.after
+    }
+}
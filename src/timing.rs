@@ -0,0 +1,47 @@
+use ddc::TimingMessage;
+
+/// Decoded reply to Get Timing Report: the timing of the video signal the monitor receives
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct TimingReport {
+    /// Horizontal frequency, in kHz
+    pub horizontal_frequency: f64,
+    /// Vertical frequency, in Hz
+    pub vertical_frequency: f64,
+    /// The sync frequencies are out of the range the monitor supports
+    pub out_of_range: bool,
+    /// The monitor has not counted a stable sync frequency yet
+    pub unstable_count: bool,
+    /// The horizontal sync polarity is positive
+    pub horizontal_sync_positive: bool,
+    /// The vertical sync polarity is positive
+    pub vertical_sync_positive: bool,
+    /// The raw status byte
+    pub status: u8,
+}
+
+impl TimingReport {
+    /// Decodes a timing report from its status byte, the horizontal frequency in 10 Hz units and the vertical
+    /// frequency in 0.01 Hz units
+    pub fn new(status: u8, horizontal_frequency: u16, vertical_frequency: u16) -> Self {
+        TimingReport {
+            horizontal_frequency: horizontal_frequency as f64 / 100.0,
+            vertical_frequency: vertical_frequency as f64 / 100.0,
+            out_of_range: status & 0x80 != 0,
+            unstable_count: status & 0x40 != 0,
+            horizontal_sync_positive: status & 0x02 != 0,
+            vertical_sync_positive: status & 0x01 != 0,
+            status,
+        }
+    }
+}
+
+impl From<TimingMessage> for TimingReport {
+    fn from(message: TimingMessage) -> Self {
+        Self::new(
+            message.timing_status,
+            message.horizontal_frequency,
+            message.vertical_frequency,
+        )
+    }
+}
@@ -0,0 +1,247 @@
+use crate::error::Error;
+use crate::monitor::Monitor;
+use crate::vcp::{FeatureKind, VcpFeature};
+use ddc::{Ddc, ErrorCode, FeatureCode};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+/// Shortest time between two steps of a transition on the same monitor: the time MCCS gives a monitor to process
+/// Set VCP Feature
+pub const MIN_STEP_INTERVAL: Duration = Duration::from_millis(50);
+
+/// How a transition progresses over its duration
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum Easing {
+    /// Constant speed
+    Linear,
+    /// Starts slowly, then accelerates
+    EaseIn,
+    /// Starts quickly, then decelerates
+    EaseOut,
+    /// Starts and ends slowly
+    #[default]
+    EaseInOut,
+}
+
+impl Easing {
+    /// Progress of the value, from 0 to 1, at a given progress in time, from 0 to 1
+    pub fn apply(self, t: f64) -> f64 {
+        let t = t.clamp(0.0, 1.0);
+        match self {
+            Easing::Linear => t,
+            Easing::EaseIn => t * t * t,
+            Easing::EaseOut => 1.0 - (1.0 - t).powi(3),
+            Easing::EaseInOut => t * t * (3.0 - 2.0 * t),
+        }
+    }
+}
+
+/// A token to cancel transitions, possibly from another thread. Clones share their state.
+#[derive(Debug, Clone, Default)]
+pub struct CancelToken {
+    cancelled: Arc<AtomicBool>,
+}
+
+impl CancelToken {
+    /// A token that is not cancelled yet
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Cancel the transitions using this token. They stop before their next step, leaving the value where it is.
+    pub fn cancel(&self) {
+        self.cancelled.store(true, Ordering::SeqCst);
+    }
+
+    /// Whether the token was cancelled
+    pub fn is_cancelled(&self) -> bool {
+        self.cancelled.load(Ordering::SeqCst)
+    }
+}
+
+/// An animated change of a continuous VCP feature, such as luminance, from its current value to a target value
+#[derive(Debug, Clone)]
+pub struct Transition {
+    feature: FeatureCode,
+    target: u16,
+    duration: Duration,
+    easing: Easing,
+    cancel: Option<CancelToken>,
+}
+
+impl Transition {
+    /// A transition of `feature` to `target` over `duration`, with [Easing::EaseInOut]
+    pub fn new(feature: impl Into<FeatureCode>, target: u16, duration: Duration) -> Self {
+        Transition {
+            feature: feature.into(),
+            target,
+            duration,
+            easing: Easing::default(),
+            cancel: None,
+        }
+    }
+
+    /// Set the easing curve
+    pub fn with_easing(self, easing: Easing) -> Self {
+        Transition { easing, ..self }
+    }
+
+    /// Make the transition cancellable with `cancel`
+    pub fn with_cancel(self, cancel: CancelToken) -> Self {
+        Transition {
+            cancel: Some(cancel),
+            ..self
+        }
+    }
+
+    /// VCP code of the feature
+    pub fn feature(&self) -> FeatureCode {
+        self.feature
+    }
+
+    /// Target value
+    pub fn target(&self) -> u16 {
+        self.target
+    }
+
+    /// Duration of the transition
+    pub fn duration(&self) -> Duration {
+        self.duration
+    }
+
+    /// Easing curve
+    pub fn easing(&self) -> Easing {
+        self.easing
+    }
+
+    fn is_cancelled(&self) -> bool {
+        self.cancel.as_ref().is_some_and(CancelToken::is_cancelled)
+    }
+}
+
+/// The outcome of a transition
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct TransitionReport {
+    /// VCP code of the feature
+    pub feature: FeatureCode,
+    /// Value before the transition
+    pub from: u16,
+    /// Target value, limited to the maximum of the feature
+    pub to: u16,
+    /// Last value set: the target unless the transition was cancelled
+    pub reached: u16,
+    /// Number of Set VCP Feature commands sent
+    pub steps: u32,
+    /// Whether the transition was cancelled before reaching its target
+    pub cancelled: bool,
+    /// Time the transition took
+    pub elapsed: Duration,
+}
+
+/// Progress of the transition of one monitor
+pub(crate) struct Progress {
+    start: Instant,
+    next_step: Instant,
+    /// Average time of a command on this monitor, which steps are spaced by at least
+    round_trip: Duration,
+    report: TransitionReport,
+    done: bool,
+}
+
+impl Progress {
+    /// Reads the current value of the feature, timing the command to estimate the round-trip time of the monitor
+    fn start(monitor: &mut Monitor, transition: &Transition) -> Result<Self, Error> {
+        if let Some(feature) = VcpFeature::from_code(transition.feature) {
+            if feature.kind() != FeatureKind::Continuous {
+                return Err(ErrorCode::Invalid(format!("{} is not a continuous feature", feature)).into());
+            }
+        }
+        let start = Instant::now();
+        let value = monitor.get_vcp_feature(transition.feature)?;
+        let now = Instant::now();
+        Ok(Progress {
+            start,
+            next_step: now + MIN_STEP_INTERVAL,
+            round_trip: now - start,
+            report: TransitionReport {
+                feature: transition.feature,
+                from: value.value(),
+                to: transition.target.min(value.maximum()),
+                reached: value.value(),
+                steps: 0,
+                cancelled: false,
+                elapsed: Duration::ZERO,
+            },
+            done: false,
+        })
+    }
+
+    /// Sets the value due now, if it differs from the last one set
+    fn step(&mut self, monitor: &mut Monitor, transition: &Transition) -> Result<(), Error> {
+        let report = &mut self.report;
+        if report.reached == report.to {
+            self.done = true;
+            return Ok(());
+        }
+        if transition.is_cancelled() {
+            report.cancelled = true;
+            self.done = true;
+            return Ok(());
+        }
+        let step_start = Instant::now();
+        let t = if transition.duration.is_zero() {
+            1.0
+        } else {
+            (step_start - self.start).as_secs_f64() / transition.duration.as_secs_f64()
+        };
+        let delta = (report.to as f64 - report.from as f64) * transition.easing.apply(t);
+        let value = (report.from as f64 + delta).round() as u16;
+        if value != report.reached {
+            monitor.set_vcp_feature(transition.feature, value)?;
+            report.reached = value;
+            report.steps += 1;
+            let now = Instant::now();
+            // A moving average, so that a single slow command does not slow the whole transition down
+            self.round_trip = (self.round_trip * 3 + (now - step_start)) / 4;
+        }
+        self.done = report.reached == report.to;
+        self.next_step = Instant::now() + MIN_STEP_INTERVAL.max(self.round_trip);
+        Ok(())
+    }
+
+    /// Runs the transition to its end on the calling thread, sleeping between steps
+    pub(crate) fn run(monitor: &mut Monitor, transition: &Transition) -> Result<TransitionReport, Error> {
+        let mut progress = Progress::start(monitor, transition)?;
+        while !progress.done {
+            let now = Instant::now();
+            if progress.next_step > now && !transition.is_cancelled() {
+                std::thread::sleep(progress.next_step - now);
+            }
+            progress.step(monitor, transition)?;
+        }
+        Ok(TransitionReport {
+            elapsed: progress.start.elapsed(),
+            ..progress.report
+        })
+    }
+}
+
+/// Run transitions on several monitors in parallel, returning the outcome of each.
+///
+/// Each monitor runs its transition on its own thread, so that a slow bus does not hold back the other monitors.
+/// Steps of a monitor are spaced by at least [MIN_STEP_INTERVAL] and by the measured round-trip time of its
+/// commands, so that slow buses get fewer, larger steps instead of a backlog of commands. A transition that fails
+/// stops with its error without affecting the others. Transitions sharing a [CancelToken] are all cancelled together.
+pub fn run_transitions(transitions: &mut [(&mut Monitor, Transition)]) -> Vec<Result<TransitionReport, Error>> {
+    std::thread::scope(|scope| {
+        let threads: Vec<_> = transitions
+            .iter_mut()
+            .map(|(monitor, transition)| scope.spawn(move || Progress::run(monitor, transition)))
+            .collect();
+        threads
+            .into_iter()
+            .map(|thread| thread.join().unwrap_or_else(|panic| std::panic::resume_unwind(panic)))
+            .collect()
+    })
+}
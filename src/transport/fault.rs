@@ -0,0 +1,178 @@
+use crate::error::Error;
+use crate::transport::DdcTransport;
+use std::collections::VecDeque;
+use std::time::Duration;
+
+/// A failure that a [FaultInjector] can apply to a DDC/CI exchange.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Fault {
+    /// Corrupt the checksum byte of the reply
+    BadChecksum,
+    /// Cut the reply down to the given number of bytes
+    Truncate(usize),
+    /// Replace the reply with a DDC/CI null message
+    NullMessage,
+    /// Replace the reply with zeroes, as returned by a read that did not reach the display
+    ZeroedReply,
+    /// Fail with the given `kIOReturn` code after the request was sent, like a failed `IOAVServiceReadI2C`
+    Io(i32),
+    /// Wait for the given time before passing the request through
+    Delay(Duration),
+}
+
+#[derive(Debug)]
+enum Schedule {
+    Script(VecDeque<Option<Fault>>),
+    Random {
+        probability: f64,
+        faults: Vec<Fault>,
+        rng: XorShift,
+    },
+}
+
+/// A transport wrapper that injects failures into the exchanges of another transport.
+///
+/// Faults are either taken from a deterministic script, one entry per request, or picked at random with a given
+/// probability from a seeded generator, so that failing runs can be reproduced.
+#[derive(Debug)]
+pub struct FaultInjector<T> {
+    inner: T,
+    schedule: Schedule,
+    injected: usize,
+}
+
+impl<T: DdcTransport> FaultInjector<T> {
+    /// Apply faults from `script` to consecutive requests, `None` letting a request through untouched. Requests
+    /// made after the script is exhausted are not affected.
+    pub fn scripted(inner: T, script: impl IntoIterator<Item = Option<Fault>>) -> Self {
+        FaultInjector {
+            inner,
+            schedule: Schedule::Script(script.into_iter().collect()),
+            injected: 0,
+        }
+    }
+
+    /// Apply one of `faults`, chosen uniformly, to each request with the given `probability`.
+    pub fn random(inner: T, probability: f64, faults: Vec<Fault>, seed: u64) -> Self {
+        FaultInjector {
+            inner,
+            schedule: Schedule::Random {
+                probability,
+                faults,
+                rng: XorShift::new(seed),
+            },
+            injected: 0,
+        }
+    }
+
+    /// Number of faults injected so far. Faults corrupting the reply are not counted when drawn for a request without
+    /// a reply, as they leave it untouched.
+    pub fn injected(&self) -> usize {
+        self.injected
+    }
+
+    /// Returns the wrapped transport
+    pub fn into_inner(self) -> T {
+        self.inner
+    }
+
+    fn next_fault(&mut self) -> Option<Fault> {
+        match &mut self.schedule {
+            Schedule::Script(script) => script.pop_front().flatten(),
+            Schedule::Random {
+                probability,
+                faults,
+                rng,
+            } => {
+                if faults.is_empty() || rng.next_f64() >= *probability {
+                    None
+                } else {
+                    Some(faults[rng.next_u64() as usize % faults.len()].clone())
+                }
+            }
+        }
+    }
+}
+
+impl<T: DdcTransport> DdcTransport for FaultInjector<T> {
+    fn execute<'a>(
+        &mut self,
+        i2c_address: u16,
+        packet: &[u8],
+        out: &'a mut [u8],
+        response_delay: Duration,
+    ) -> Result<&'a mut [u8], Error> {
+        let fault = match self.next_fault() {
+            Some(fault) => fault,
+            None => return self.inner.execute(i2c_address, packet, out, response_delay),
+        };
+        if let Fault::Delay(delay) = fault {
+            std::thread::sleep(delay);
+        }
+        let response = self.inner.execute(i2c_address, packet, out, response_delay)?;
+        // Faults corrupting the reply do not apply to requests without one, such as Set VCP Feature
+        let applied = match fault {
+            Fault::Io(code) => {
+                self.injected += 1;
+                return Err(Error::Io(code));
+            }
+            Fault::Delay(_) => true,
+            _ => !response.is_empty(),
+        };
+        if !applied {
+            return Ok(response);
+        }
+        self.injected += 1;
+        match fault {
+            Fault::BadChecksum => {
+                let index = 2 + (response.get(1).copied().unwrap_or_default() & 0x7f) as usize;
+                if let Some(checksum) = response.get_mut(index) {
+                    *checksum ^= 0xff;
+                }
+                Ok(response)
+            }
+            Fault::Truncate(len) => {
+                let len = len.min(response.len());
+                Ok(&mut response[..len])
+            }
+            Fault::NullMessage => {
+                response.fill(0);
+                let null_message = [(i2c_address << 1) as u8, 0x80, 0x50 ^ ((i2c_address << 1) as u8) ^ 0x80];
+                let len = null_message.len().min(response.len());
+                response[..len].copy_from_slice(&null_message[..len]);
+                Ok(response)
+            }
+            Fault::ZeroedReply => {
+                response.fill(0);
+                Ok(response)
+            }
+            Fault::Io(_) | Fault::Delay(_) => Ok(response),
+        }
+    }
+
+    fn flush(&mut self) -> Result<(), Error> {
+        self.inner.flush()
+    }
+}
+
+/// Small deterministic generator, good enough to pick faults
+#[derive(Debug)]
+struct XorShift(u64);
+
+impl XorShift {
+    fn new(seed: u64) -> Self {
+        // Zero is the one state xorshift never leaves
+        XorShift(seed.max(1))
+    }
+
+    fn next_u64(&mut self) -> u64 {
+        self.0 ^= self.0 << 13;
+        self.0 ^= self.0 >> 7;
+        self.0 ^= self.0 << 17;
+        self.0
+    }
+
+    fn next_f64(&mut self) -> f64 {
+        (self.next_u64() >> 11) as f64 / (1u64 << 53) as f64
+    }
+}
@@ -0,0 +1,58 @@
+//! Transports carry encoded DDC/CI packets between a [Monitor](crate::Monitor) and a display.
+//!
+//! The monitors returned by `Monitor::enumerate` talk to the hardware through IOKit, but any
+//! [DdcTransport] can be plugged in with [Monitor::with_transport](crate::Monitor::with_transport).
+
+mod fault;
+mod record;
+mod simulated;
+
+pub use fault::{Fault, FaultInjector};
+pub use record::{Exchange, Recorder, Replayer, Reply, Session, SESSION_VERSION};
+pub use simulated::SimulatedMonitor;
+
+use crate::error::Error;
+use std::fmt;
+use std::time::Duration;
+
+/// A channel able to exchange raw DDC/CI packets with a display.
+///
+/// Packets passed to [DdcTransport::execute] are already framed by the [Monitor](crate::Monitor): they start
+/// with the DDC/CI sub-address, followed by the length byte, the command and the checksum. Replies are returned
+/// as read from the bus, including the source address and length bytes, and are validated by the caller.
+///
+/// Transports are [Send], so that a [Monitor](crate::Monitor) can be moved to another thread.
+pub trait DdcTransport: fmt::Debug + Send {
+    /// Writes `packet` to the device at `i2c_address`, waits for `response_delay` and reads the reply into `out`.
+    ///
+    /// No reply should be read if `out` is empty, in which case an empty slice is returned.
+    fn execute<'a>(
+        &mut self,
+        i2c_address: u16,
+        packet: &[u8],
+        out: &'a mut [u8],
+        response_delay: Duration,
+    ) -> Result<&'a mut [u8], Error>;
+
+    /// Completes any pending work of the transport, such as writing a recording, and returns the errors it hit that
+    /// could not be reported by [DdcTransport::execute]. Does nothing by default.
+    fn flush(&mut self) -> Result<(), Error> {
+        Ok(())
+    }
+}
+
+impl<T: DdcTransport + ?Sized> DdcTransport for Box<T> {
+    fn execute<'a>(
+        &mut self,
+        i2c_address: u16,
+        packet: &[u8],
+        out: &'a mut [u8],
+        response_delay: Duration,
+    ) -> Result<&'a mut [u8], Error> {
+        (**self).execute(i2c_address, packet, out, response_delay)
+    }
+
+    fn flush(&mut self) -> Result<(), Error> {
+        (**self).flush()
+    }
+}
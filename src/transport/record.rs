@@ -0,0 +1,356 @@
+use crate::error::{Error, ErrorKind};
+use crate::transport::DdcTransport;
+use ddc::ErrorCode;
+use std::collections::VecDeque;
+use std::fmt::Write as _;
+use std::fs::File;
+use std::io::{self, BufRead, BufReader, Read, Write};
+use std::path::Path;
+use std::time::Duration;
+
+/// First line of every recorded session file, followed by the format version
+const SESSION_HEADER: &str = "ddc-macos session";
+
+/// Version of the session file format written by this crate
+pub const SESSION_VERSION: u32 = 1;
+
+/// Outcome of a recorded DDC/CI exchange
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Reply {
+    /// Bytes returned by the transport, empty if no reply was read
+    Data(Vec<u8>),
+    /// The transport failed with a kernel I/O error
+    Io(i32),
+    /// The transport failed with another error
+    Failed {
+        /// Kind of the error
+        kind: ErrorKind,
+        /// What else is needed to rebuild the error: the code of a Core Graphics error, the message of an invalid
+        /// request error, or the expected and actual values of a mismatch in hex, separated by a comma
+        detail: String,
+    },
+}
+
+impl Reply {
+    /// The reply recording a transport error
+    pub fn from_error(error: &Error) -> Self {
+        let detail = match error {
+            Error::Io(code) => return Reply::Io(*code),
+            Error::CoreGraphics(code) => code.to_string(),
+            Error::Ddc(ErrorCode::Invalid(message)) => message.clone(),
+            Error::SourceAddressMismatch { expected, actual } | Error::OpcodeMismatch { expected, actual } => {
+                format!("{:02x},{:02x}", expected, actual)
+            }
+            _ => String::new(),
+        };
+        Reply::Failed {
+            kind: error.kind(),
+            detail,
+        }
+    }
+
+    /// The error a failed reply records, `None` for data replies or if the details cannot be parsed
+    pub fn to_error(&self) -> Option<Error> {
+        let (kind, detail) = match self {
+            Reply::Data(_) => return None,
+            Reply::Io(code) => return Some(Error::Io(*code)),
+            Reply::Failed { kind, detail } => (*kind, detail),
+        };
+        let mismatch = || {
+            let (expected, actual) = detail.split_once(',')?;
+            Some((
+                u8::from_str_radix(expected, 16).ok()?,
+                u8::from_str_radix(actual, 16).ok()?,
+            ))
+        };
+        Some(match kind {
+            ErrorKind::CoreGraphics => Error::CoreGraphics(detail.parse().ok()?),
+            ErrorKind::Io => Error::Io(detail.parse().ok()?),
+            ErrorKind::InvalidOffset => ErrorCode::InvalidOffset.into(),
+            ErrorKind::InvalidLength => ErrorCode::InvalidLength.into(),
+            ErrorKind::InvalidChecksum => ErrorCode::InvalidChecksum.into(),
+            ErrorKind::InvalidOpcode => ErrorCode::InvalidOpcode.into(),
+            ErrorKind::InvalidData => ErrorCode::InvalidData.into(),
+            ErrorKind::Invalid => ErrorCode::Invalid(detail.clone()).into(),
+            ErrorKind::ServiceNotFound => Error::ServiceNotFound,
+            ErrorKind::DisplayLocationNotFound => Error::DisplayLocationNotFound,
+            ErrorKind::MonitorBusy => Error::MonitorBusy,
+            ErrorKind::SourceAddressMismatch => {
+                let (expected, actual) = mismatch()?;
+                Error::SourceAddressMismatch { expected, actual }
+            }
+            ErrorKind::OpcodeMismatch => {
+                let (expected, actual) = mismatch()?;
+                Error::OpcodeMismatch { expected, actual }
+            }
+        })
+    }
+}
+
+/// A single request and its reply, as seen by a transport
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Exchange {
+    /// I2C address of the device
+    pub i2c_address: u16,
+    /// Delay requested between the write and the read
+    pub response_delay: Duration,
+    /// Encoded request packet
+    pub request: Vec<u8>,
+    /// What the transport returned
+    pub reply: Reply,
+}
+
+/// A recorded sequence of DDC/CI exchanges.
+///
+/// Sessions are stored as text: a `ddc-macos session <version>` header, then one line per exchange holding the
+/// hexadecimal I2C address, the response delay in microseconds, the request in hex and the reply. The reply is
+/// either hex data, `-` when no reply was read, `!io:<code>` for a kernel I/O error or `!error:<kind>:<detail>` for
+/// other errors, the kind being the name of its [ErrorKind] and the detail as described in [Reply::Failed]. Empty lines
+/// and lines starting with `#` are ignored.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Session {
+    /// Recorded exchanges, in order
+    pub exchanges: Vec<Exchange>,
+}
+
+impl Session {
+    /// Read a session from a file
+    pub fn load(path: impl AsRef<Path>) -> io::Result<Self> {
+        Self::read_from(File::open(path)?)
+    }
+
+    /// Write a session to a file
+    pub fn save(&self, path: impl AsRef<Path>) -> io::Result<()> {
+        self.write_to(File::create(path)?)
+    }
+
+    /// Read a session in the text format described in [Session]
+    pub fn read_from(reader: impl Read) -> io::Result<Self> {
+        let mut lines = BufReader::new(reader).lines();
+        let header = lines.next().transpose()?.unwrap_or_default();
+        let version = header
+            .strip_prefix(SESSION_HEADER)
+            .and_then(|version| version.trim().parse::<u32>().ok())
+            .ok_or_else(|| invalid_data("not a DDC/CI session file"))?;
+        if version != SESSION_VERSION {
+            return Err(invalid_data(&format!("unsupported session version {}", version)));
+        }
+        let mut exchanges = Vec::new();
+        for line in lines {
+            let line = line?;
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+            exchanges.push(parse_exchange(line).ok_or_else(|| invalid_data(&format!("invalid exchange: {}", line)))?);
+        }
+        Ok(Session { exchanges })
+    }
+
+    /// Write a session in the text format described in [Session]
+    pub fn write_to(&self, mut writer: impl Write) -> io::Result<()> {
+        write_header(&mut writer)?;
+        for exchange in &self.exchanges {
+            writeln!(writer, "{}", format_exchange(exchange))?;
+        }
+        writer.flush()
+    }
+}
+
+/// A transport wrapper that records every exchange of another transport.
+///
+/// Exchanges are appended to the writer as they happen, so the recording survives a crash of the program under
+/// investigation. The result can be read back with [Session::read_from]. Failing to write the recording does not
+/// affect the exchanges themselves: the first write error is kept, and returned by [Recorder::finish], or by
+/// [DdcTransport::flush] once the recorder is owned by a [Monitor](crate::Monitor).
+#[derive(Debug)]
+pub struct Recorder<T, W: Write> {
+    inner: T,
+    writer: W,
+    write_error: Option<io::Error>,
+}
+
+impl<T: DdcTransport, W: Write> Recorder<T, W> {
+    /// Start recording exchanges of `inner` to `writer`
+    pub fn new(inner: T, mut writer: W) -> io::Result<Self> {
+        write_header(&mut writer)?;
+        writer.flush()?;
+        Ok(Recorder {
+            inner,
+            writer,
+            write_error: None,
+        })
+    }
+
+    /// Returns the wrapped transport and the writer
+    pub fn into_inner(self) -> (T, W) {
+        (self.inner, self.writer)
+    }
+
+    /// Returns the wrapped transport and the writer, or the first error writing the recording
+    pub fn finish(self) -> io::Result<(T, W)> {
+        match self.write_error {
+            Some(error) => Err(error),
+            None => Ok((self.inner, self.writer)),
+        }
+    }
+}
+
+impl<T: DdcTransport, W: Write + std::fmt::Debug + Send> DdcTransport for Recorder<T, W> {
+    fn execute<'a>(
+        &mut self,
+        i2c_address: u16,
+        packet: &[u8],
+        out: &'a mut [u8],
+        response_delay: Duration,
+    ) -> Result<&'a mut [u8], Error> {
+        let result = self.inner.execute(i2c_address, packet, out, response_delay);
+        let reply = match &result {
+            Ok(response) => Reply::Data(response.to_vec()),
+            Err(error) => Reply::from_error(error),
+        };
+        let exchange = Exchange {
+            i2c_address,
+            response_delay,
+            request: packet.to_vec(),
+            reply,
+        };
+        // The exchange happened whether or not it can be recorded, so its result is returned regardless
+        let written = writeln!(self.writer, "{}", format_exchange(&exchange)).and_then(|_| self.writer.flush());
+        if let Err(error) = written {
+            self.write_error.get_or_insert(error);
+        }
+        result
+    }
+
+    fn flush(&mut self) -> Result<(), Error> {
+        if let Some(error) = self.write_error.take() {
+            return Err(error.into());
+        }
+        self.writer.flush()?;
+        self.inner.flush()
+    }
+}
+
+/// A transport that serves the replies of a recorded [Session].
+///
+/// Each request must match the next recorded request, otherwise the replay fails with an invalid data error. No
+/// delays are waited for.
+#[derive(Debug)]
+pub struct Replayer {
+    exchanges: VecDeque<Exchange>,
+}
+
+impl Replayer {
+    /// Replay the exchanges of `session`, in order
+    pub fn new(session: Session) -> Self {
+        Replayer {
+            exchanges: session.exchanges.into(),
+        }
+    }
+
+    /// Number of recorded exchanges not replayed yet
+    pub fn remaining(&self) -> usize {
+        self.exchanges.len()
+    }
+}
+
+impl DdcTransport for Replayer {
+    fn execute<'a>(
+        &mut self,
+        i2c_address: u16,
+        packet: &[u8],
+        out: &'a mut [u8],
+        _response_delay: Duration,
+    ) -> Result<&'a mut [u8], Error> {
+        let exchange = self
+            .exchanges
+            .pop_front()
+            .ok_or_else(|| ErrorCode::Invalid("no more recorded exchanges".into()))?;
+        if exchange.i2c_address != i2c_address || exchange.request != packet {
+            return Err(ErrorCode::Invalid("request does not match the recorded session".into()).into());
+        }
+        match exchange.reply {
+            Reply::Data(data) => {
+                if data.len() > out.len() {
+                    return Err(ErrorCode::InvalidLength.into());
+                }
+                out[..data.len()].copy_from_slice(&data);
+                Ok(&mut out[..data.len()])
+            }
+            reply => Err(reply
+                .to_error()
+                .unwrap_or_else(|| ErrorCode::Invalid("invalid recorded error".into()).into())),
+        }
+    }
+}
+
+fn write_header(writer: &mut impl Write) -> io::Result<()> {
+    writeln!(writer, "{} {}", SESSION_HEADER, SESSION_VERSION)
+}
+
+fn format_exchange(exchange: &Exchange) -> String {
+    let reply = match &exchange.reply {
+        Reply::Data(data) if data.is_empty() => "-".to_string(),
+        Reply::Data(data) => to_hex(data),
+        Reply::Io(code) => format!("!io:{}", code),
+        Reply::Failed { kind, detail } => format!("!error:{}:{}", kind.name(), detail.replace('\n', " ")),
+    };
+    format!(
+        "{:02x} {} {} {}",
+        exchange.i2c_address,
+        exchange.response_delay.as_micros(),
+        to_hex(&exchange.request),
+        reply
+    )
+}
+
+fn parse_exchange(line: &str) -> Option<Exchange> {
+    let mut fields = line.splitn(4, ' ');
+    let i2c_address = u16::from_str_radix(fields.next()?, 16).ok()?;
+    let response_delay = Duration::from_micros(fields.next()?.parse().ok()?);
+    let request = from_hex(fields.next()?)?;
+    let reply = fields.next()?;
+    let reply = if reply == "-" {
+        Reply::Data(Vec::new())
+    } else if let Some(code) = reply.strip_prefix("!io:") {
+        Reply::Io(code.parse().ok()?)
+    } else if let Some(error) = reply.strip_prefix("!error:") {
+        let (name, detail) = error.split_once(':')?;
+        let kind = name.parse().ok()?;
+        Reply::Failed {
+            kind,
+            detail: detail.to_string(),
+        }
+    } else {
+        Reply::Data(from_hex(reply)?)
+    };
+    Some(Exchange {
+        i2c_address,
+        response_delay,
+        request,
+        reply,
+    })
+}
+
+fn to_hex(data: &[u8]) -> String {
+    data.iter()
+        .fold(String::with_capacity(data.len() * 2), |mut hex, byte| {
+            let _ = write!(hex, "{:02x}", byte);
+            hex
+        })
+}
+
+fn from_hex(hex: &str) -> Option<Vec<u8>> {
+    hex.as_bytes()
+        .chunks(2)
+        .map(|pair| match pair {
+            [_, _] => u8::from_str_radix(std::str::from_utf8(pair).ok()?, 16).ok(),
+            _ => None,
+        })
+        .collect()
+}
+
+fn invalid_data(message: &str) -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidData, message)
+}
@@ -0,0 +1,425 @@
+use crate::codec::{checksum, MAX_FRAGMENT_LEN};
+use crate::error::Error;
+use crate::lut::{GammaLut, LutChannel, LutSize};
+use crate::transport::DdcTransport;
+use ddc::{FeatureCode, VcpValue, SUB_ADDRESS_DDC_CI};
+use std::collections::{BTreeMap, BTreeSet};
+use std::iter;
+use std::sync::{Arc, Mutex, MutexGuard};
+use std::time::Duration;
+
+/// Capabilities string reported by a default [SimulatedMonitor]
+const DEFAULT_CAPABILITIES: &str = "(prot(monitor)type(lcd)model(SIM1)cmds(01 02 03 07 0C E3 F3)\
+    vcp(10 12 60(0F 11 12) 62 D6(01 04 05) DF)mccs_ver(2.2))";
+
+/// Virtual host address used to compute reply checksums
+const HOST_ADDRESS: u8 = 0x50;
+
+#[derive(Debug, Clone, Copy)]
+struct Feature {
+    value: u16,
+    maximum: u16,
+    factory: u16,
+}
+
+/// Color lookup tables, with the state of the LUT operations reading them
+#[derive(Debug)]
+struct Lut {
+    tables: GammaLut,
+    /// Entry read by Single Point LUT operations
+    point: u16,
+    /// Channel number and first entry read by Block LUT operations
+    block: (u8, u16),
+    /// Block LUT operation being written, fragment by fragment
+    block_write: Vec<u8>,
+}
+
+#[derive(Debug)]
+struct State {
+    features: BTreeMap<FeatureCode, Feature>,
+    read_only: BTreeSet<FeatureCode>,
+    capabilities: Vec<u8>,
+    tables: BTreeMap<FeatureCode, Vec<u8>>,
+    lut: Option<Lut>,
+    timing_status: u8,
+    horizontal_frequency: u16,
+    vertical_frequency: u16,
+    latency: Duration,
+    settings_saved: u32,
+}
+
+/// An in-memory display that answers DDC/CI requests the way a real MCCS monitor does.
+///
+/// The simulated monitor understands Get VCP Feature, Set VCP Feature, Save Current Settings, Capabilities Request,
+/// Get Timing Report, Table Read and Table Write, and replies with properly framed and checksummed packets. Any other
+/// request, or a request with a bad checksum, is answered with a DDC/CI null message. The factory reset features
+/// restore the values features were created with. Monitors with color lookup tables support LUT Size and the Single
+/// Point and Block LUT operations (VCP 0x73 to 0x75).
+///
+/// Clones share their state, so a clone kept by a test can inspect the values set through a
+/// [Monitor](crate::Monitor) that owns another clone.
+#[derive(Debug, Clone)]
+pub struct SimulatedMonitor {
+    state: Arc<Mutex<State>>,
+}
+
+impl Default for SimulatedMonitor {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl SimulatedMonitor {
+    /// Create a simulated monitor with a typical set of features: luminance, contrast, input source, audio volume,
+    /// power mode and a read-only MCCS version.
+    pub fn new() -> Self {
+        Self::empty()
+            .with_feature(0x10, 50, 100)
+            .with_feature(0x12, 75, 100)
+            .with_feature(0x60, 0x0f, 0x12)
+            .with_feature(0x62, 30, 100)
+            .with_feature(0xd6, 0x01, 0x05)
+            .with_read_only_feature(0xdf, 0x0202, 0xffff)
+            .with_capabilities(DEFAULT_CAPABILITIES)
+    }
+
+    /// Create a simulated monitor without any VCP features and an empty capabilities string.
+    pub fn empty() -> Self {
+        SimulatedMonitor {
+            state: Arc::new(Mutex::new(State {
+                features: BTreeMap::new(),
+                read_only: BTreeSet::new(),
+                capabilities: Vec::new(),
+                tables: BTreeMap::new(),
+                lut: None,
+                timing_status: 0x03,
+                horizontal_frequency: 0x3a98,
+                vertical_frequency: 0x1770,
+                latency: Duration::ZERO,
+                settings_saved: 0,
+            })),
+        }
+    }
+
+    /// Add or replace a writable VCP feature with its current and maximum values.
+    pub fn with_feature(self, code: FeatureCode, value: u16, maximum: u16) -> Self {
+        {
+            let mut state = self.state();
+            state.features.insert(
+                code,
+                Feature {
+                    value,
+                    maximum,
+                    factory: value,
+                },
+            );
+            state.read_only.remove(&code);
+        }
+        self
+    }
+
+    /// Add or replace a VCP feature that ignores Set VCP Feature requests.
+    pub fn with_read_only_feature(self, code: FeatureCode, value: u16, maximum: u16) -> Self {
+        let this = self.with_feature(code, value, maximum);
+        this.state().read_only.insert(code);
+        this
+    }
+
+    /// Remove a VCP feature, so that reading it reports an unsupported VCP code.
+    pub fn without_feature(self, code: FeatureCode) -> Self {
+        {
+            let mut state = self.state();
+            state.features.remove(&code);
+            state.read_only.remove(&code);
+        }
+        self
+    }
+
+    /// Replace the capabilities string returned by Capabilities Request.
+    pub fn with_capabilities(self, capabilities: impl Into<Vec<u8>>) -> Self {
+        self.state().capabilities = capabilities.into();
+        self
+    }
+
+    /// Add or replace a table feature with its contents. Table Write can extend the table, but not create it.
+    pub fn with_table(self, code: FeatureCode, table: impl Into<Vec<u8>>) -> Self {
+        self.state().tables.insert(code, table.into());
+        self
+    }
+
+    /// Add color lookup tables of the given size, initially leaving colors unchanged.
+    pub fn with_lut(self, size: LutSize) -> Self {
+        self.state().lut = Some(Lut {
+            tables: GammaLut::identity(size),
+            point: 0,
+            block: (1, 0),
+            block_write: Vec::new(),
+        });
+        self
+    }
+
+    /// Set the timing report: status byte, horizontal frequency in 10 Hz units and vertical frequency in 0.01 Hz
+    /// units.
+    pub fn with_timing_report(self, status: u8, horizontal_frequency: u16, vertical_frequency: u16) -> Self {
+        {
+            let mut state = self.state();
+            state.timing_status = status;
+            state.horizontal_frequency = horizontal_frequency;
+            state.vertical_frequency = vertical_frequency;
+        }
+        self
+    }
+
+    /// Delay every request by `latency`. The response delay requested by the caller is never waited for.
+    pub fn with_latency(self, latency: Duration) -> Self {
+        self.state().latency = latency;
+        self
+    }
+
+    /// Number of Save Current Settings requests received.
+    pub fn settings_saved(&self) -> u32 {
+        self.state().settings_saved
+    }
+
+    /// Current state of a VCP feature, if the monitor supports it.
+    pub fn feature(&self, code: FeatureCode) -> Option<VcpValue> {
+        self.state().features.get(&code).map(|feature| VcpValue {
+            ty: 0,
+            mh: (feature.maximum >> 8) as u8,
+            ml: feature.maximum as u8,
+            sh: (feature.value >> 8) as u8,
+            sl: feature.value as u8,
+        })
+    }
+
+    /// Current contents of a table feature, if the monitor supports it.
+    pub fn table(&self, code: FeatureCode) -> Option<Vec<u8>> {
+        self.state().tables.get(&code).cloned()
+    }
+
+    /// Current color lookup tables, if the monitor has any.
+    pub fn lut(&self) -> Option<GammaLut> {
+        self.state().lut.as_ref().map(|lut| lut.tables.clone())
+    }
+
+    fn state(&self) -> MutexGuard<'_, State> {
+        self.state.lock().unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+
+    /// Parses a request packet, returning the command bytes if it is well-formed.
+    fn parse_request(i2c_address: u16, packet: &[u8]) -> Option<&[u8]> {
+        if packet.len() < 3 || packet[0] != SUB_ADDRESS_DDC_CI || packet[1] & 0x80 == 0 {
+            return None;
+        }
+        let len = (packet[1] & 0x7f) as usize;
+        if packet.len() < len + 3 {
+            return None;
+        }
+        let checksum = checksum(iter::once((i2c_address << 1) as u8).chain(packet[..2 + len].iter().cloned()));
+        if packet[2 + len] != checksum {
+            return None;
+        }
+        Some(&packet[2..2 + len])
+    }
+
+    /// Handles a command, returning the reply payload if the command produces one.
+    fn handle(state: &mut State, command: &[u8]) -> Option<Vec<u8>> {
+        match command {
+            [0x01, code] => Some(match state.features.get(code) {
+                Some(feature) => vec![
+                    0x02,
+                    0x00,
+                    *code,
+                    0x00,
+                    (feature.maximum >> 8) as u8,
+                    feature.maximum as u8,
+                    (feature.value >> 8) as u8,
+                    feature.value as u8,
+                ],
+                None => vec![0x02, 0x01, *code, 0x00, 0x00, 0x00, 0x00, 0x00],
+            }),
+            // Restore factory defaults, luminance/contrast, geometry or color, with any non-zero value
+            [0x03, reset @ (0x04 | 0x05 | 0x06 | 0x08), value_high, value_low]
+                if (*value_high, *value_low) != (0, 0) =>
+            {
+                let restored = |code: &FeatureCode| match reset {
+                    0x05 => matches!(code, 0x10 | 0x12),
+                    0x06 => matches!(code, 0x20..=0x4c),
+                    0x08 => matches!(code, 0x0c | 0x14 | 0x16 | 0x18 | 0x1a | 0x6c | 0x6e | 0x70),
+                    _ => true,
+                };
+                for (_, feature) in state.features.iter_mut().filter(|(code, _)| restored(code)) {
+                    feature.value = feature.factory;
+                }
+                None
+            }
+            [0x03, code, value_high, value_low] => {
+                if !state.read_only.contains(code) {
+                    if let Some(feature) = state.features.get_mut(code) {
+                        feature.value = u16::from_be_bytes([*value_high, *value_low]).min(feature.maximum);
+                    }
+                }
+                None
+            }
+            [0x07] => {
+                let mut reply = vec![0x4e, state.timing_status];
+                reply.extend_from_slice(&state.horizontal_frequency.to_be_bytes());
+                reply.extend_from_slice(&state.vertical_frequency.to_be_bytes());
+                Some(reply)
+            }
+            [0x0c] => {
+                state.settings_saved += 1;
+                None
+            }
+            [0xe2, code, offset_high, offset_low] => Some(match Self::table_contents(state, *code) {
+                Some(table) => {
+                    let offset = u16::from_be_bytes([*offset_high, *offset_low]) as usize;
+                    let start = offset.min(table.len());
+                    let end = (start + MAX_FRAGMENT_LEN).min(table.len());
+                    let mut reply = vec![0xe4, *offset_high, *offset_low];
+                    reply.extend_from_slice(&table[start..end]);
+                    reply
+                }
+                None => Vec::new(),
+            }),
+            [0xe7, code @ (0x74 | 0x75), offset_high, offset_low, data @ ..] if state.lut.is_some() => {
+                let offset = u16::from_be_bytes([*offset_high, *offset_low]) as usize;
+                if let Some(lut) = &mut state.lut {
+                    Self::write_lut(lut, *code, offset, data);
+                }
+                None
+            }
+            [0xe7, code, offset_high, offset_low, data @ ..] => {
+                if let Some(table) = state.tables.get_mut(code) {
+                    let offset = u16::from_be_bytes([*offset_high, *offset_low]) as usize;
+                    if table.len() < offset + data.len() {
+                        table.resize(offset + data.len(), 0);
+                    }
+                    table[offset..offset + data.len()].copy_from_slice(data);
+                }
+                None
+            }
+            [0xf3, offset_high, offset_low] => {
+                let offset = u16::from_be_bytes([*offset_high, *offset_low]) as usize;
+                let start = offset.min(state.capabilities.len());
+                let end = (start + MAX_FRAGMENT_LEN).min(state.capabilities.len());
+                let mut reply = vec![0xe3, *offset_high, *offset_low];
+                reply.extend_from_slice(&state.capabilities[start..end]);
+                Some(reply)
+            }
+            _ => Some(Vec::new()),
+        }
+    }
+
+    /// Contents of a table feature, as read by Table Read
+    fn table_contents(state: &State, code: FeatureCode) -> Option<Vec<u8>> {
+        match (code, &state.lut) {
+            (0x73, Some(lut)) => Some(lut.tables.size().to_bytes().to_vec()),
+            (0x74, Some(lut)) => {
+                let mut point = lut.point.to_be_bytes().to_vec();
+                for channel in LutChannel::ALL {
+                    let value = lut
+                        .tables
+                        .channel(channel)
+                        .get(lut.point as usize)
+                        .copied()
+                        .unwrap_or(0);
+                    point.extend_from_slice(&value.to_be_bytes());
+                }
+                Some(point)
+            }
+            (0x75, Some(lut)) => {
+                let (channel, start) = lut.block;
+                let table = LutChannel::from_number(channel).map_or(&[][..], |channel| lut.tables.channel(channel));
+                let start = (start as usize).min(table.len());
+                Some(table[start..].iter().flat_map(|value| value.to_be_bytes()).collect())
+            }
+            _ => state.tables.get(&code).cloned(),
+        }
+    }
+
+    /// Applies a Table Write fragment of a Single Point or Block LUT operation. A 2-byte single point or 3-byte block
+    /// operation selects the entries read next, longer ones load entries.
+    fn write_lut(lut: &mut Lut, code: FeatureCode, offset: usize, data: &[u8]) {
+        let size = lut.tables.size();
+        let mut tables = LutChannel::ALL.map(|channel| lut.tables.channel(channel).to_vec());
+        let mut load = |channel: u8, entry: usize, value: &[u8]| {
+            if let Some(channel) = LutChannel::from_number(channel) {
+                let value = u16::from_be_bytes([value[0], value[1]]).min(size.maximum(channel));
+                if let Some(slot) = tables[channel.number() as usize - 1].get_mut(entry) {
+                    *slot = value;
+                }
+            }
+        };
+        match (code, data.len()) {
+            (0x74, 2) => lut.point = u16::from_be_bytes([data[0], data[1]]),
+            (0x74, 8) => {
+                let entry = u16::from_be_bytes([data[0], data[1]]) as usize;
+                for (channel, value) in (1..=3).zip(data[2..].chunks_exact(2)) {
+                    load(channel, entry, value);
+                }
+            }
+            (0x75, _) => {
+                if offset == 0 {
+                    lut.block_write.clear();
+                }
+                if offset != lut.block_write.len() {
+                    return;
+                }
+                lut.block_write.extend_from_slice(data);
+                let block = &lut.block_write;
+                if block.len() < 3 {
+                    return;
+                }
+                let start = u16::from_be_bytes([block[1], block[2]]);
+                if block.len() == 3 {
+                    lut.block = (block[0], start);
+                }
+                for (index, value) in block[3..].chunks_exact(2).enumerate() {
+                    load(block[0], start as usize + index, value);
+                }
+            }
+            _ => return,
+        }
+        if let Ok(tables) = GammaLut::from_values(size, tables) {
+            lut.tables = tables;
+        }
+    }
+
+    /// Frames a reply payload the way a display puts it on the bus: source address, length, payload, checksum.
+    fn write_reply(i2c_address: u16, payload: &[u8], out: &mut [u8]) {
+        let mut reply = Vec::with_capacity(payload.len() + 3);
+        reply.push((i2c_address << 1) as u8);
+        reply.push(0x80 | payload.len() as u8);
+        reply.extend_from_slice(payload);
+        reply.push(checksum(iter::once(HOST_ADDRESS).chain(reply.iter().cloned())));
+        let len = reply.len().min(out.len());
+        out[..len].copy_from_slice(&reply[..len]);
+    }
+}
+
+impl DdcTransport for SimulatedMonitor {
+    fn execute<'a>(
+        &mut self,
+        i2c_address: u16,
+        packet: &[u8],
+        out: &'a mut [u8],
+        _response_delay: Duration,
+    ) -> Result<&'a mut [u8], Error> {
+        let mut state = self.state();
+        if !state.latency.is_zero() {
+            std::thread::sleep(state.latency);
+        }
+        // Malformed requests are answered with a null message, like a display that could not process them
+        let reply = match Self::parse_request(i2c_address, packet) {
+            Some(command) => Self::handle(&mut state, command),
+            None => Some(Vec::new()),
+        };
+        if out.is_empty() {
+            return Ok(out);
+        }
+        out.fill(0);
+        Self::write_reply(i2c_address, &reply.unwrap_or_default(), out);
+        Ok(out)
+    }
+}
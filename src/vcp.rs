@@ -0,0 +1,601 @@
+use crate::error::Error;
+use ddc::{ErrorCode, FeatureCode};
+use std::fmt;
+
+/// How the value of a VCP feature is interpreted
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum FeatureKind {
+    /// A value anywhere between zero and the maximum reported by the monitor
+    Continuous,
+    /// One of a set of values with specific meanings
+    NonContinuous,
+    /// A block of bytes, read and written with table commands
+    Table,
+}
+
+/// Whether a VCP feature can be read, written or both
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum FeatureAccess {
+    /// The feature can be read and written
+    ReadWrite,
+    /// The feature can only be read
+    ReadOnly,
+    /// The feature can only be written, typically to trigger an action
+    WriteOnly,
+}
+
+/// A VCP feature defined by MCCS 2.2a.
+///
+/// Features convert to their [FeatureCode] with [VcpFeature::code] or `u8::from`, so they can be used with the raw
+/// [ddc::Ddc] methods. Codes of features not listed here, such as manufacturer specific ones in the E0-FF range, can
+/// still be used directly.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum VcpFeature {
+    /// Code page used by the monitor for new control values
+    NewControlValue,
+    /// Restore all factory defaults
+    RestoreFactoryDefaults,
+    /// Restore factory luminance and contrast
+    RestoreFactoryLuminanceContrast,
+    /// Restore factory geometry
+    RestoreFactoryGeometry,
+    /// Restore factory color
+    RestoreFactoryColor,
+    /// Restore factory TV defaults
+    RestoreFactoryTvDefaults,
+    /// Color temperature increment
+    ColorTemperatureIncrement,
+    /// Color temperature request
+    ColorTemperatureRequest,
+    /// Clock
+    Clock,
+    /// Luminance, also known as brightness
+    Luminance,
+    /// Contrast
+    Contrast,
+    /// Backlight control
+    BacklightControl,
+    /// Color preset
+    ColorPreset,
+    /// Red video gain
+    RedGain,
+    /// Green video gain
+    GreenGain,
+    /// Blue video gain
+    BlueGain,
+    /// Active control: the code of the feature last changed on the monitor
+    ActiveControl,
+    /// Input source
+    InputSource,
+    /// Audio speaker volume
+    AudioVolume,
+    /// Red video black level
+    RedBlackLevel,
+    /// Green video black level
+    GreenBlackLevel,
+    /// Blue video black level
+    BlueBlackLevel,
+    /// Gamma
+    Gamma,
+    /// Size of the color lookup tables
+    LutSize,
+    /// Single entry of the color lookup tables
+    SinglePointLut,
+    /// Block of entries of a color lookup table
+    BlockLut,
+    /// Sharpness
+    Sharpness,
+    /// Color saturation
+    Saturation,
+    /// Audio mute
+    AudioMute,
+    /// Audio treble
+    AudioTreble,
+    /// Hue
+    Hue,
+    /// Audio bass
+    AudioBass,
+    /// Screen orientation
+    ScreenOrientation,
+    /// Horizontal frequency
+    HorizontalFrequency,
+    /// Vertical frequency
+    VerticalFrequency,
+    /// Save or restore settings
+    SaveRestoreSettings,
+    /// Display technology type
+    DisplayTechnologyType,
+    /// Display usage time in hours
+    DisplayUsageTime,
+    /// Application enable key
+    ApplicationEnableKey,
+    /// Display controller type
+    DisplayControllerType,
+    /// Display firmware level
+    DisplayFirmwareLevel,
+    /// On screen display language
+    OsdLanguage,
+    /// Power mode
+    PowerMode,
+    /// Display mode, also known as picture mode
+    DisplayMode,
+    /// MCCS version implemented by the monitor
+    VcpVersion,
+}
+
+impl VcpFeature {
+    /// All features, in order of their codes
+    pub const ALL: [VcpFeature; 45] = [
+        VcpFeature::NewControlValue,
+        VcpFeature::RestoreFactoryDefaults,
+        VcpFeature::RestoreFactoryLuminanceContrast,
+        VcpFeature::RestoreFactoryGeometry,
+        VcpFeature::RestoreFactoryColor,
+        VcpFeature::RestoreFactoryTvDefaults,
+        VcpFeature::ColorTemperatureIncrement,
+        VcpFeature::ColorTemperatureRequest,
+        VcpFeature::Clock,
+        VcpFeature::Luminance,
+        VcpFeature::Contrast,
+        VcpFeature::BacklightControl,
+        VcpFeature::ColorPreset,
+        VcpFeature::RedGain,
+        VcpFeature::GreenGain,
+        VcpFeature::BlueGain,
+        VcpFeature::ActiveControl,
+        VcpFeature::InputSource,
+        VcpFeature::AudioVolume,
+        VcpFeature::RedBlackLevel,
+        VcpFeature::GreenBlackLevel,
+        VcpFeature::BlueBlackLevel,
+        VcpFeature::Gamma,
+        VcpFeature::LutSize,
+        VcpFeature::SinglePointLut,
+        VcpFeature::BlockLut,
+        VcpFeature::Sharpness,
+        VcpFeature::Saturation,
+        VcpFeature::AudioMute,
+        VcpFeature::AudioTreble,
+        VcpFeature::Hue,
+        VcpFeature::AudioBass,
+        VcpFeature::ScreenOrientation,
+        VcpFeature::HorizontalFrequency,
+        VcpFeature::VerticalFrequency,
+        VcpFeature::SaveRestoreSettings,
+        VcpFeature::DisplayTechnologyType,
+        VcpFeature::DisplayUsageTime,
+        VcpFeature::ApplicationEnableKey,
+        VcpFeature::DisplayControllerType,
+        VcpFeature::DisplayFirmwareLevel,
+        VcpFeature::OsdLanguage,
+        VcpFeature::PowerMode,
+        VcpFeature::DisplayMode,
+        VcpFeature::VcpVersion,
+    ];
+
+    /// The feature with the given code, `None` if it is not one of the features listed here
+    pub fn from_code(code: FeatureCode) -> Option<Self> {
+        VcpFeature::ALL.iter().copied().find(|feature| feature.code() == code)
+    }
+
+    /// VCP code of the feature
+    pub fn code(self) -> FeatureCode {
+        self.info().0
+    }
+
+    /// Short name of the feature, as used in the MCCS specification
+    pub fn name(self) -> &'static str {
+        self.info().1
+    }
+
+    /// One-line description of the feature
+    pub fn description(self) -> &'static str {
+        self.info().2
+    }
+
+    /// How values of the feature are interpreted
+    pub fn kind(self) -> FeatureKind {
+        self.info().3
+    }
+
+    /// Whether the feature can be read, written or both
+    pub fn access(self) -> FeatureAccess {
+        self.info().4
+    }
+
+    #[rustfmt::skip]
+    fn info(self) -> (FeatureCode, &'static str, &'static str, FeatureKind, FeatureAccess) {
+        use FeatureAccess::*;
+        use FeatureKind::*;
+        match self {
+            VcpFeature::NewControlValue => (0x02, "New Control Value", "Whether controls were changed on the monitor", NonContinuous, ReadWrite),
+            VcpFeature::RestoreFactoryDefaults => (0x04, "Restore Factory Defaults", "Restore all factory presets", NonContinuous, WriteOnly),
+            VcpFeature::RestoreFactoryLuminanceContrast => (0x05, "Restore Factory Luminance/Contrast", "Restore factory luminance and contrast", NonContinuous, WriteOnly),
+            VcpFeature::RestoreFactoryGeometry => (0x06, "Restore Factory Geometry", "Restore factory geometry adjustments", NonContinuous, WriteOnly),
+            VcpFeature::RestoreFactoryColor => (0x08, "Restore Factory Color", "Restore factory color adjustments", NonContinuous, WriteOnly),
+            VcpFeature::RestoreFactoryTvDefaults => (0x0a, "Restore Factory TV Defaults", "Restore factory TV adjustments", NonContinuous, WriteOnly),
+            VcpFeature::ColorTemperatureIncrement => (0x0b, "Color Temperature Increment", "Step in kelvins of color temperature requests", Continuous, ReadOnly),
+            VcpFeature::ColorTemperatureRequest => (0x0c, "Color Temperature Request", "Color temperature, in increments above 3000 K", Continuous, ReadWrite),
+            VcpFeature::Clock => (0x0e, "Clock", "Sampling clock frequency", Continuous, ReadWrite),
+            VcpFeature::Luminance => (0x10, "Luminance", "Brightness of the image", Continuous, ReadWrite),
+            VcpFeature::BacklightControl => (0x13, "Backlight Control", "Backlight level", Continuous, ReadWrite),
+            VcpFeature::Contrast => (0x12, "Contrast", "Contrast of the image", Continuous, ReadWrite),
+            VcpFeature::ColorPreset => (0x14, "Select Color Preset", "Color temperature or color space preset", NonContinuous, ReadWrite),
+            VcpFeature::RedGain => (0x16, "Video Gain: Red", "Red sub-pixel luminance", Continuous, ReadWrite),
+            VcpFeature::GreenGain => (0x18, "Video Gain: Green", "Green sub-pixel luminance", Continuous, ReadWrite),
+            VcpFeature::BlueGain => (0x1a, "Video Gain: Blue", "Blue sub-pixel luminance", Continuous, ReadWrite),
+            VcpFeature::ActiveControl => (0x52, "Active Control", "Code of a feature changed on the monitor", NonContinuous, ReadOnly),
+            VcpFeature::InputSource => (0x60, "Input Select", "Video input the monitor displays", NonContinuous, ReadWrite),
+            VcpFeature::AudioVolume => (0x62, "Audio: Speaker Volume", "Speaker volume", Continuous, ReadWrite),
+            VcpFeature::RedBlackLevel => (0x6c, "Video Black Level: Red", "Red black level", Continuous, ReadWrite),
+            VcpFeature::GreenBlackLevel => (0x6e, "Video Black Level: Green", "Green black level", Continuous, ReadWrite),
+            VcpFeature::BlueBlackLevel => (0x70, "Video Black Level: Blue", "Blue black level", Continuous, ReadWrite),
+            VcpFeature::Gamma => (0x72, "Gamma", "Display gamma", NonContinuous, ReadWrite),
+            VcpFeature::LutSize => (0x73, "LUT Size", "Entries and bits per entry of the color LUTs", Table, ReadOnly),
+            VcpFeature::SinglePointLut => (0x74, "Single Point LUT Operation", "Load or read one entry of the color LUTs", Table, ReadWrite),
+            VcpFeature::BlockLut => (0x75, "Block LUT Operation", "Load or read consecutive entries of a color LUT", Table, ReadWrite),
+            VcpFeature::AudioTreble => (0x8f, "Audio: Treble", "Treble", Continuous, ReadWrite),
+            VcpFeature::AudioBass => (0x91, "Audio: Bass", "Bass", Continuous, ReadWrite),
+            VcpFeature::Sharpness => (0x87, "Sharpness", "Sharpness of the image", Continuous, ReadWrite),
+            VcpFeature::Saturation => (0x8a, "Color Saturation", "Color saturation", Continuous, ReadWrite),
+            VcpFeature::AudioMute => (0x8d, "Audio Mute", "Mute or unmute the speakers", NonContinuous, ReadWrite),
+            VcpFeature::Hue => (0x90, "Hue", "Hue of the image", Continuous, ReadWrite),
+            VcpFeature::ScreenOrientation => (0xaa, "Screen Orientation", "Orientation of the screen", NonContinuous, ReadOnly),
+            VcpFeature::HorizontalFrequency => (0xac, "Horizontal Frequency", "Horizontal sync frequency in Hz", Continuous, ReadOnly),
+            VcpFeature::VerticalFrequency => (0xae, "Vertical Frequency", "Vertical sync frequency in 0.01 Hz", Continuous, ReadOnly),
+            VcpFeature::SaveRestoreSettings => (0xb0, "Settings", "Store or restore user settings", NonContinuous, WriteOnly),
+            VcpFeature::DisplayTechnologyType => (0xb6, "Display Technology Type", "Type of display technology", NonContinuous, ReadOnly),
+            VcpFeature::DisplayUsageTime => (0xc0, "Display Usage Time", "Active hours of the display", Continuous, ReadOnly),
+            VcpFeature::ApplicationEnableKey => (0xc6, "Application Enable Key", "Key enabling manufacturer specific features", NonContinuous, ReadOnly),
+            VcpFeature::DisplayControllerType => (0xc8, "Display Controller Type", "Manufacturer and type of the controller", NonContinuous, ReadWrite),
+            VcpFeature::DisplayFirmwareLevel => (0xc9, "Display Firmware Level", "Firmware version of the controller", Continuous, ReadOnly),
+            VcpFeature::OsdLanguage => (0xcc, "OSD Language", "Language of the on screen display", NonContinuous, ReadWrite),
+            VcpFeature::PowerMode => (0xd6, "Power Mode", "Power state of the display", NonContinuous, ReadWrite),
+            VcpFeature::DisplayMode => (0xdc, "Display Application", "Picture mode for the type of content", NonContinuous, ReadWrite),
+            VcpFeature::VcpVersion => (0xdf, "VCP Version", "MCCS version implemented by the monitor", NonContinuous, ReadOnly),
+        }
+    }
+}
+
+impl From<VcpFeature> for FeatureCode {
+    fn from(feature: VcpFeature) -> Self {
+        feature.code()
+    }
+}
+
+impl fmt::Display for VcpFeature {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.name())
+    }
+}
+
+/// A typed value of a non-continuous VCP feature
+pub trait FeatureValue: Sized {
+    /// The feature holding the value
+    const FEATURE: VcpFeature;
+
+    /// Decodes the value of the feature
+    fn from_vcp(value: u16) -> Self;
+
+    /// Encodes the value for Set VCP Feature, failing if the value has no MCCS encoding
+    fn to_vcp(&self) -> Result<u16, Error>;
+}
+
+/// A video input, value of [VcpFeature::InputSource]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum InputSource {
+    /// Analog video (R/G/B) 1, usually VGA
+    Analog1,
+    /// Analog video (R/G/B) 2
+    Analog2,
+    /// Digital video (TMDS) 1, usually DVI
+    Dvi1,
+    /// Digital video (TMDS) 2
+    Dvi2,
+    /// Composite video 1
+    Composite1,
+    /// Composite video 2
+    Composite2,
+    /// S-Video 1
+    SVideo1,
+    /// S-Video 2
+    SVideo2,
+    /// Tuner 1
+    Tuner1,
+    /// Tuner 2
+    Tuner2,
+    /// Tuner 3
+    Tuner3,
+    /// Component video 1
+    Component1,
+    /// Component video 2
+    Component2,
+    /// Component video 3
+    Component3,
+    /// DisplayPort 1
+    DisplayPort1,
+    /// DisplayPort 2
+    DisplayPort2,
+    /// HDMI 1
+    Hdmi1,
+    /// HDMI 2
+    Hdmi2,
+    /// A value not defined by MCCS, such as USB-C inputs of some monitors
+    Other(u8),
+}
+
+impl InputSource {
+    /// All inputs defined by MCCS
+    pub const ALL: [InputSource; 18] = [
+        InputSource::Analog1,
+        InputSource::Analog2,
+        InputSource::Dvi1,
+        InputSource::Dvi2,
+        InputSource::Composite1,
+        InputSource::Composite2,
+        InputSource::SVideo1,
+        InputSource::SVideo2,
+        InputSource::Tuner1,
+        InputSource::Tuner2,
+        InputSource::Tuner3,
+        InputSource::Component1,
+        InputSource::Component2,
+        InputSource::Component3,
+        InputSource::DisplayPort1,
+        InputSource::DisplayPort2,
+        InputSource::Hdmi1,
+        InputSource::Hdmi2,
+    ];
+
+    /// The input with the given MCCS value
+    pub fn from_value(value: u8) -> Self {
+        InputSource::ALL
+            .iter()
+            .copied()
+            .find(|input| input.value() == value)
+            .unwrap_or(InputSource::Other(value))
+    }
+
+    /// MCCS value of the input
+    pub fn value(self) -> u8 {
+        match self {
+            InputSource::Analog1 => 0x01,
+            InputSource::Analog2 => 0x02,
+            InputSource::Dvi1 => 0x03,
+            InputSource::Dvi2 => 0x04,
+            InputSource::Composite1 => 0x05,
+            InputSource::Composite2 => 0x06,
+            InputSource::SVideo1 => 0x07,
+            InputSource::SVideo2 => 0x08,
+            InputSource::Tuner1 => 0x09,
+            InputSource::Tuner2 => 0x0a,
+            InputSource::Tuner3 => 0x0b,
+            InputSource::Component1 => 0x0c,
+            InputSource::Component2 => 0x0d,
+            InputSource::Component3 => 0x0e,
+            InputSource::DisplayPort1 => 0x0f,
+            InputSource::DisplayPort2 => 0x10,
+            InputSource::Hdmi1 => 0x11,
+            InputSource::Hdmi2 => 0x12,
+            InputSource::Other(value) => value,
+        }
+    }
+
+    /// Human readable name of the input, e.g. `HDMI 1`
+    pub fn name(self) -> String {
+        let name = match self {
+            InputSource::Analog1 => "VGA 1",
+            InputSource::Analog2 => "VGA 2",
+            InputSource::Dvi1 => "DVI 1",
+            InputSource::Dvi2 => "DVI 2",
+            InputSource::Composite1 => "Composite 1",
+            InputSource::Composite2 => "Composite 2",
+            InputSource::SVideo1 => "S-Video 1",
+            InputSource::SVideo2 => "S-Video 2",
+            InputSource::Tuner1 => "Tuner 1",
+            InputSource::Tuner2 => "Tuner 2",
+            InputSource::Tuner3 => "Tuner 3",
+            InputSource::Component1 => "Component 1",
+            InputSource::Component2 => "Component 2",
+            InputSource::Component3 => "Component 3",
+            InputSource::DisplayPort1 => "DisplayPort 1",
+            InputSource::DisplayPort2 => "DisplayPort 2",
+            InputSource::Hdmi1 => "HDMI 1",
+            InputSource::Hdmi2 => "HDMI 2",
+            InputSource::Other(value) => return format!("Input {:#04x}", value),
+        };
+        name.to_string()
+    }
+}
+
+impl FeatureValue for InputSource {
+    const FEATURE: VcpFeature = VcpFeature::InputSource;
+
+    /// Only the low byte is used: some monitors report garbage in the high byte
+    fn from_vcp(value: u16) -> Self {
+        InputSource::from_value(value as u8)
+    }
+
+    fn to_vcp(&self) -> Result<u16, Error> {
+        Ok(self.value() as u16)
+    }
+}
+
+impl fmt::Display for InputSource {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.name())
+    }
+}
+
+/// Power state of a display, value of [VcpFeature::PowerMode]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum PowerMode {
+    /// Display on
+    On,
+    /// Standby: the display wakes up quickly
+    Standby,
+    /// Suspended
+    Suspend,
+    /// Display off, DDC/CI still works
+    Off,
+    /// Powered off, as with the power button
+    PowerOff,
+    /// A value not defined by MCCS
+    Other(u8),
+}
+
+impl PowerMode {
+    /// Human readable name of the power mode
+    pub fn name(self) -> &'static str {
+        match self {
+            PowerMode::On => "On",
+            PowerMode::Standby => "Standby",
+            PowerMode::Suspend => "Suspend",
+            PowerMode::Off => "Off",
+            PowerMode::PowerOff => "Power off",
+            PowerMode::Other(_) => "Unknown",
+        }
+    }
+}
+
+impl FeatureValue for PowerMode {
+    const FEATURE: VcpFeature = VcpFeature::PowerMode;
+
+    fn from_vcp(value: u16) -> Self {
+        match value as u8 {
+            0x01 => PowerMode::On,
+            0x02 => PowerMode::Standby,
+            0x03 => PowerMode::Suspend,
+            0x04 => PowerMode::Off,
+            0x05 => PowerMode::PowerOff,
+            value => PowerMode::Other(value),
+        }
+    }
+
+    fn to_vcp(&self) -> Result<u16, Error> {
+        Ok(match self {
+            PowerMode::On => 0x01,
+            PowerMode::Standby => 0x02,
+            PowerMode::Suspend => 0x03,
+            PowerMode::Off => 0x04,
+            PowerMode::PowerOff => 0x05,
+            PowerMode::Other(value) => *value as u16,
+        })
+    }
+}
+
+impl fmt::Display for PowerMode {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.name())
+    }
+}
+
+/// Color temperature or color space preset, value of [VcpFeature::ColorPreset]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum ColorPreset {
+    /// sRGB
+    Srgb,
+    /// Native color of the panel
+    Native,
+    /// A color temperature in kelvins: 4000, 5000, 6500, 7500, 8200, 9300, 10000 or 11500
+    Temperature(u16),
+    /// User defined preset 1, 2 or 3
+    User(u8),
+    /// A value not defined by MCCS
+    Other(u8),
+}
+
+/// Color temperatures of [ColorPreset::Temperature], starting at value 0x03
+const COLOR_TEMPERATURES: [u16; 8] = [4000, 5000, 6500, 7500, 8200, 9300, 10000, 11500];
+
+impl FeatureValue for ColorPreset {
+    const FEATURE: VcpFeature = VcpFeature::ColorPreset;
+
+    fn from_vcp(value: u16) -> Self {
+        match value as u8 {
+            0x01 => ColorPreset::Srgb,
+            0x02 => ColorPreset::Native,
+            value @ 0x03..=0x0a => ColorPreset::Temperature(COLOR_TEMPERATURES[value as usize - 0x03]),
+            value @ 0x0b..=0x0d => ColorPreset::User(value - 0x0a),
+            value => ColorPreset::Other(value),
+        }
+    }
+
+    /// Fails for temperatures MCCS does not define and user presets other than 1, 2 and 3
+    fn to_vcp(&self) -> Result<u16, Error> {
+        match *self {
+            ColorPreset::Srgb => Ok(0x01),
+            ColorPreset::Native => Ok(0x02),
+            ColorPreset::Temperature(kelvins) => COLOR_TEMPERATURES
+                .iter()
+                .position(|&temperature| temperature == kelvins)
+                .map(|index| index as u16 + 0x03)
+                .ok_or_else(|| ErrorCode::Invalid(format!("no color preset for {} K", kelvins)).into()),
+            ColorPreset::User(1) => Ok(0x0b),
+            ColorPreset::User(2) => Ok(0x0c),
+            ColorPreset::User(3) => Ok(0x0d),
+            ColorPreset::User(user) => Err(ErrorCode::Invalid(format!("no user color preset {}", user)).into()),
+            ColorPreset::Other(value) => Ok(value as u16),
+        }
+    }
+}
+
+impl fmt::Display for ColorPreset {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ColorPreset::Srgb => write!(f, "sRGB"),
+            ColorPreset::Native => write!(f, "Native"),
+            ColorPreset::Temperature(kelvins) => write!(f, "{} K", kelvins),
+            ColorPreset::User(user) => write!(f, "User {}", user),
+            ColorPreset::Other(value) => write!(f, "Preset {:#04x}", value),
+        }
+    }
+}
+
+/// Speaker mute state, value of [VcpFeature::AudioMute]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum AudioMute {
+    /// Speakers muted
+    Muted,
+    /// Speakers not muted
+    Unmuted,
+    /// A value not defined by MCCS
+    Other(u8),
+}
+
+impl FeatureValue for AudioMute {
+    const FEATURE: VcpFeature = VcpFeature::AudioMute;
+
+    fn from_vcp(value: u16) -> Self {
+        match value as u8 {
+            0x01 => AudioMute::Muted,
+            0x02 => AudioMute::Unmuted,
+            value => AudioMute::Other(value),
+        }
+    }
+
+    fn to_vcp(&self) -> Result<u16, Error> {
+        Ok(match self {
+            AudioMute::Muted => 0x01,
+            AudioMute::Unmuted => 0x02,
+            AudioMute::Other(value) => *value as u16,
+        })
+    }
+}
+
+impl fmt::Display for AudioMute {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            AudioMute::Muted => write!(f, "Muted"),
+            AudioMute::Unmuted => write!(f, "Unmuted"),
+            AudioMute::Other(value) => write!(f, "Mute {:#04x}", value),
+        }
+    }
+}
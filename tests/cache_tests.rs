@@ -0,0 +1,114 @@
+extern crate ddc_macos;
+
+use ddc::I2C_ADDRESS_DDC_CI;
+use ddc_macos::transport::SimulatedMonitor;
+use ddc_macos::{CacheKey, CapabilitiesCache, Monitor};
+use std::path::PathBuf;
+
+const DELL_EDID: &[u8] = include_bytes!("edid/dell.edid.bin");
+
+fn cache_directory(name: &str) -> PathBuf {
+    std::env::temp_dir().join(format!("ddc-macos-cache-{}-{}", name, std::process::id()))
+}
+
+fn monitor(simulated: SimulatedMonitor, cache: &CapabilitiesCache) -> Monitor {
+    let mut monitor = Monitor::with_transport(simulated, I2C_ADDRESS_DDC_CI);
+    monitor.set_edid(DELL_EDID.to_vec());
+    monitor.set_capabilities_cache(Some(cache.clone()));
+    monitor
+}
+
+#[test]
+fn test_cache_key_from_edid() {
+    let key = CacheKey::from_edid(DELL_EDID).unwrap();
+    assert_eq!(key.vendor, "DEL");
+    assert_eq!(key.product, 0xa1e4);
+    assert_eq!(key.serial, "#G7QYMxgwABxd");
+    assert_eq!(CacheKey::from_edid(&DELL_EDID[..64]), None);
+}
+
+#[test]
+fn test_capabilities_are_kept_in_memory() {
+    let simulated = SimulatedMonitor::new();
+    let mut monitor = Monitor::with_transport(simulated.clone(), I2C_ADDRESS_DDC_CI);
+    assert_eq!(monitor.capabilities().unwrap().model.as_deref(), Some("SIM1"));
+    simulated.clone().with_capabilities("(model(SIM2))");
+    assert_eq!(monitor.capabilities().unwrap().model.as_deref(), Some("SIM1"));
+    monitor.invalidate_capabilities().unwrap();
+    assert_eq!(monitor.capabilities().unwrap().model.as_deref(), Some("SIM2"));
+}
+
+#[test]
+fn test_capabilities_are_cached_on_disk() {
+    let directory = cache_directory("disk");
+    let cache = CapabilitiesCache::new(&directory);
+    let key = CacheKey::from_edid(DELL_EDID).unwrap();
+
+    let mut first = monitor(SimulatedMonitor::new(), &cache);
+    assert_eq!(first.capabilities().unwrap().model.as_deref(), Some("SIM1"));
+    assert!(cache.load(&key).is_some());
+
+    // Another run: the monitor is not asked again
+    let mut second = monitor(SimulatedMonitor::empty(), &cache);
+    assert_eq!(second.capabilities().unwrap().model.as_deref(), Some("SIM1"));
+
+    second.invalidate_capabilities().unwrap();
+    assert_eq!(cache.load(&key), None);
+    assert_eq!(second.capabilities().unwrap().model, None);
+    std::fs::remove_dir_all(&directory).unwrap();
+}
+
+#[test]
+fn test_incomplete_capabilities_are_not_cached() {
+    let directory = cache_directory("incomplete");
+    let cache = CapabilitiesCache::new(&directory);
+    let key = CacheKey::from_edid(DELL_EDID).unwrap();
+
+    // A reply cut before the model and the VCP features
+    let simulated = SimulatedMonitor::new().with_capabilities("(prot(monitor)type(lcd)");
+    let mut monitor = monitor(simulated.clone(), &cache);
+    assert_eq!(monitor.capabilities().unwrap().display_type.as_deref(), Some("lcd"));
+    assert_eq!(cache.load(&key), None);
+    // Nor kept in memory: the monitor is asked again
+    simulated.clone().with_capabilities("(model(SIM2)vcp(10))");
+    assert_eq!(monitor.capabilities().unwrap().model.as_deref(), Some("SIM2"));
+    assert!(cache.load(&key).is_some());
+
+    // Incomplete entries already in the cache are ignored
+    cache.store(&key, b"").unwrap();
+    let mut monitor = self::monitor(SimulatedMonitor::new(), &cache);
+    assert_eq!(monitor.capabilities().unwrap().model.as_deref(), Some("SIM1"));
+    std::fs::remove_dir_all(&directory).unwrap();
+}
+
+#[test]
+fn test_no_disk_cache_without_edid() {
+    let directory = cache_directory("no-edid");
+    let mut monitor = Monitor::with_transport(SimulatedMonitor::new(), I2C_ADDRESS_DDC_CI);
+    monitor.set_capabilities_cache(Some(CapabilitiesCache::new(&directory)));
+    assert!(monitor.capabilities().is_ok());
+    assert!(!directory.exists());
+}
+
+#[test]
+fn test_store_invalidate_clear() {
+    let directory = cache_directory("clear");
+    let cache = CapabilitiesCache::new(&directory);
+    assert!(cache.clear().is_ok());
+    let key = CacheKey {
+        vendor: "ABC".into(),
+        product: 1,
+        serial: "../serial".into(),
+    };
+    cache.store(&key, b"(model(A))").unwrap();
+    assert_eq!(cache.load(&key).as_deref(), Some(&b"(model(A))"[..]));
+    assert_eq!(std::fs::read_dir(&directory).unwrap().count(), 1);
+    cache.invalidate(&key).unwrap();
+    cache.invalidate(&key).unwrap();
+    assert_eq!(cache.load(&key), None);
+
+    cache.store(&key, b"(model(A))").unwrap();
+    cache.clear().unwrap();
+    assert_eq!(cache.load(&key), None);
+    std::fs::remove_dir_all(&directory).unwrap();
+}
@@ -0,0 +1,123 @@
+extern crate ddc_macos;
+
+use ddc::I2C_ADDRESS_DDC_CI;
+use ddc_macos::transport::SimulatedMonitor;
+use ddc_macos::{Capabilities, MccsVersion, Monitor};
+
+const CORPUS: [(&str, &[u8]); 5] = [
+    ("dell-u2720q", include_bytes!("capabilities/dell-u2720q.txt")),
+    ("dell-s2721dgf", include_bytes!("capabilities/dell-s2721dgf.txt")),
+    ("lg-27gl850", include_bytes!("capabilities/lg-27gl850.txt")),
+    ("benq-gw2765", include_bytes!("capabilities/benq-gw2765.txt")),
+    ("samsung-s24", include_bytes!("capabilities/samsung-s24.txt")),
+];
+
+fn corpus(name: &str) -> Capabilities {
+    let (_, data) = CORPUS.iter().find(|(file, _)| *file == name).unwrap();
+    Capabilities::parse(data)
+}
+
+#[test]
+fn test_corpus_basics() {
+    for (name, data) in CORPUS {
+        let capabilities = Capabilities::parse(data);
+        assert_eq!(capabilities.protocol.as_deref(), Some("monitor"), "{}", name);
+        assert!(
+            capabilities
+                .display_type
+                .as_deref()
+                .unwrap()
+                .eq_ignore_ascii_case("lcd"),
+            "{}",
+            name
+        );
+        assert!(capabilities.supports_command(0x01), "{}", name);
+        assert!(
+            capabilities.supports_vcp(0x10) && capabilities.supports_vcp(0x12),
+            "{}",
+            name
+        );
+        assert!(capabilities.vcp_values(0x60).unwrap().len() >= 2, "{}", name);
+        assert!(capabilities.mccs_version.is_some(), "{}", name);
+    }
+}
+
+#[test]
+fn test_dell() {
+    let capabilities = corpus("dell-u2720q");
+    assert_eq!(capabilities.model.as_deref(), Some("U2720Q"));
+    assert_eq!(capabilities.mccs_version, Some(MccsVersion { major: 2, minor: 1 }));
+    assert_eq!(capabilities.commands, [0x01, 0x02, 0x03, 0x07, 0x0c, 0xe3, 0xf3]);
+    assert_eq!(capabilities.vcp_values(0x60), Some(&[0x0f, 0x11, 0x1b][..]));
+    assert_eq!(capabilities.vcp_values(0x10), Some(&[][..]));
+    assert_eq!(capabilities.vcp_values(0xe2).unwrap().len(), 10);
+    assert_eq!(capabilities.vcp_features.len(), 31);
+    assert!(!capabilities.supports_vcp(0x62));
+    assert_eq!(capabilities.other("mswhql"), Some("1"));
+    assert_eq!(capabilities.other("asset_eep"), Some("40"));
+}
+
+#[test]
+fn test_model_with_spaces() {
+    let capabilities = corpus("lg-27gl850");
+    assert_eq!(capabilities.model.as_deref(), Some("LG Monitor"));
+    assert_eq!(capabilities.vcp_values(0x15).unwrap().len(), 12);
+    assert!(capabilities.supports_vcp(0xff));
+}
+
+#[test]
+fn test_missing_closing_parenthesis() {
+    let capabilities = corpus("benq-gw2765");
+    assert_eq!(capabilities.mccs_version, Some(MccsVersion { major: 2, minor: 2 }));
+    assert_eq!(capabilities.vcp_values(0xdc).unwrap().len(), 9);
+    assert!(capabilities.supports_vcp(0xff));
+}
+
+#[test]
+fn test_stray_text() {
+    let capabilities = corpus("samsung-s24");
+    assert_eq!(capabilities.model, None);
+    assert_eq!(capabilities.commands.len(), 7);
+    assert_eq!(capabilities.vcp_values(0x60).unwrap().len(), 8);
+}
+
+#[test]
+fn test_unseparated_lowercase_codes() {
+    let capabilities = corpus("dell-s2721dgf");
+    assert_eq!(capabilities.commands, [0x01, 0x02, 0x03, 0x0c, 0xe3, 0xf3]);
+    let codes: Vec<_> = capabilities.vcp_features.keys().copied().collect();
+    assert_eq!(codes, [0x02, 0x04, 0x05, 0x10, 0x12, 0x14, 0x60, 0xd6]);
+    assert_eq!(capabilities.vcp_values(0x60), Some(&[0x0f, 0x1b][..]));
+    assert_eq!(capabilities.vcp_names.get(&0xe0).map(String::as_str), Some("Game Mode"));
+    assert_eq!(capabilities.mccs_version, Some(MccsVersion { major: 2, minor: 2 }));
+}
+
+#[test]
+fn test_repeated_vcp_entries_are_merged() {
+    let capabilities = Capabilities::parse(b"(vcp(10 60(0F 11))vcp(12 60(11 12)))");
+    let codes: Vec<_> = capabilities.vcp_features.keys().copied().collect();
+    assert_eq!(codes, [0x10, 0x12, 0x60]);
+    assert_eq!(capabilities.vcp_values(0x60), Some(&[0x0f, 0x11, 0x12][..]));
+}
+
+#[test]
+fn test_garbage() {
+    assert_eq!(Capabilities::parse(b""), Capabilities::default());
+    assert_eq!(Capabilities::parse(b"\xff\xfe)))((("), Capabilities::default());
+    let capabilities = Capabilities::parse(b"(model(X)vcp(10 1");
+    assert_eq!(capabilities.model.as_deref(), Some("X"));
+    assert!(capabilities.supports_vcp(0x10) && capabilities.supports_vcp(0x01));
+}
+
+#[test]
+fn test_mccs_version_display() {
+    assert_eq!(MccsVersion { major: 2, minor: 2 }.to_string(), "2.2");
+}
+
+#[test]
+fn test_monitor_capabilities() {
+    let mut monitor = Monitor::with_transport(SimulatedMonitor::new(), I2C_ADDRESS_DDC_CI);
+    let capabilities = monitor.capabilities().unwrap();
+    assert_eq!(capabilities.model.as_deref(), Some("SIM1"));
+    assert_eq!(capabilities.vcp_values(0xd6), Some(&[0x01, 0x04, 0x05][..]));
+}
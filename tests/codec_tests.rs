@@ -0,0 +1,258 @@
+//! Tests of the DDC/CI framing. Like the rest of the crate, the codec is tested from here through its public API:
+//! `Encoder`, `Decoder` and `checksum` are all there is to it, so every length, checksum and address path can be
+//! reached without access to its internals.
+
+extern crate ddc_macos;
+
+use ddc::{ErrorCode, I2C_ADDRESS_DDC_CI};
+use ddc_macos::codec::{checksum, reply_opcode, Decoder, Encoder, MAX_DATA_LEN, PACKET_OVERHEAD};
+use ddc_macos::Error;
+
+const I2C_ADDRESS_DDC_CI_MDCP29XX: u16 = 0xb7;
+
+/// Frames a reply the way a display sends it
+fn reply(data: &[u8]) -> Vec<u8> {
+    let mut reply = vec![0x6e, 0x80 | data.len() as u8];
+    reply.extend_from_slice(data);
+    reply.push(checksum(std::iter::once(0x50).chain(reply.iter().cloned())));
+    reply
+}
+
+#[test]
+fn test_checksum() {
+    assert_eq!(checksum([]), 0);
+    assert_eq!(checksum([0x6e, 0x51, 0x82, 0x01, 0x10]), 0xac);
+    assert_eq!(checksum([0xff, 0xff]), 0);
+}
+
+#[test]
+fn test_encode_get_vcp_feature() {
+    let mut packet = [0u8; MAX_DATA_LEN + PACKET_OVERHEAD];
+    let packet = Encoder::new(I2C_ADDRESS_DDC_CI).encode(&[0x01, 0x10], &mut packet);
+    assert_eq!(packet, [0x51, 0x82, 0x01, 0x10, 0xac]);
+}
+
+#[test]
+fn test_encode_set_vcp_feature() {
+    let mut packet = [0u8; MAX_DATA_LEN + PACKET_OVERHEAD];
+    let packet = Encoder::new(I2C_ADDRESS_DDC_CI).encode(&[0x03, 0x10, 0x00, 0x32], &mut packet);
+    assert_eq!(packet, [0x51, 0x84, 0x03, 0x10, 0x00, 0x32, 0x9a]);
+}
+
+#[test]
+fn test_encode_mdcp29xx_address() {
+    // Only the low 7 bits of the address end up on the wire, so 0xB7 frames exactly like 0x37
+    let mut standard = [0u8; MAX_DATA_LEN + PACKET_OVERHEAD];
+    let mut mdcp29xx = [0u8; MAX_DATA_LEN + PACKET_OVERHEAD];
+    let data = [0xf3, 0x00, 0x20];
+    assert_eq!(
+        Encoder::new(I2C_ADDRESS_DDC_CI_MDCP29XX).encode(&data, &mut mdcp29xx),
+        Encoder::new(I2C_ADDRESS_DDC_CI).encode(&data, &mut standard)
+    );
+}
+
+#[test]
+fn test_encode_empty_and_maximum_data() {
+    let mut packet = [0u8; MAX_DATA_LEN + PACKET_OVERHEAD];
+    assert_eq!(
+        Encoder::new(I2C_ADDRESS_DDC_CI).encode(&[], &mut packet),
+        [0x51, 0x80, 0xbf]
+    );
+
+    let data = [0xaa; MAX_DATA_LEN];
+    let packet = Encoder::new(I2C_ADDRESS_DDC_CI).encode(&data, &mut packet);
+    assert_eq!(packet.len(), MAX_DATA_LEN + PACKET_OVERHEAD);
+    assert_eq!(packet[1], 0x80 | MAX_DATA_LEN as u8);
+    assert_eq!(checksum(std::iter::once(0x6e).chain(packet.iter().cloned())), 0);
+}
+
+#[test]
+#[should_panic]
+fn test_encode_too_long() {
+    let mut packet = [0u8; 64];
+    Encoder::new(I2C_ADDRESS_DDC_CI).encode(&[0u8; MAX_DATA_LEN + 1], &mut packet);
+}
+
+#[test]
+fn test_decode_round_trip() {
+    for address in [I2C_ADDRESS_DDC_CI, I2C_ADDRESS_DDC_CI_MDCP29XX] {
+        let decoder = Decoder::new(address);
+        assert_eq!(decoder.i2c_address(), address);
+        for len in 1..=32 {
+            let data: Vec<u8> = (0..len as u8).map(|i| i.wrapping_mul(37)).collect();
+            let mut response = reply(&data);
+            assert_eq!(decoder.decode(&mut response).unwrap(), data.as_slice());
+        }
+    }
+}
+
+#[test]
+fn test_decode_null_message() {
+    for address in [I2C_ADDRESS_DDC_CI, I2C_ADDRESS_DDC_CI_MDCP29XX] {
+        let mut response = [0x6e, 0x80, 0xbe, 0x00, 0x00];
+        assert!(matches!(
+            Decoder::new(address).decode(&mut response),
+            Err(Error::MonitorBusy)
+        ));
+    }
+    // A zeroed buffer is not a null message: nothing was read from the display
+    let mut zeroed = [0u8; 11];
+    assert!(matches!(
+        Decoder::new(I2C_ADDRESS_DDC_CI).decode(&mut zeroed),
+        Err(Error::Ddc(ErrorCode::InvalidChecksum))
+    ));
+}
+
+#[test]
+fn test_decode_ignores_trailing_bytes() {
+    let mut response = reply(&[0x02, 0x00, 0x10, 0x00, 0x00, 0x64, 0x00, 0x32]);
+    response.extend_from_slice(&[0, 0, 0]);
+    let decoded = Decoder::new(I2C_ADDRESS_DDC_CI).decode(&mut response).unwrap();
+    assert_eq!(decoded, [0x02, 0x00, 0x10, 0x00, 0x00, 0x64, 0x00, 0x32]);
+}
+
+#[test]
+fn test_decode_empty() {
+    assert!(Decoder::new(I2C_ADDRESS_DDC_CI).decode(&mut []).unwrap().is_empty());
+}
+
+#[test]
+fn test_decode_invalid_length() {
+    let decoder = Decoder::new(I2C_ADDRESS_DDC_CI);
+    let full = reply(&[0x02, 0x00, 0x10, 0x00, 0x00, 0x64, 0x00, 0x32]);
+    for len in 1..full.len() {
+        let mut truncated = full[..len].to_vec();
+        assert!(matches!(
+            decoder.decode(&mut truncated),
+            Err(Error::Ddc(ErrorCode::InvalidLength))
+        ));
+    }
+}
+
+#[test]
+fn test_decode_invalid_checksum() {
+    let decoder = Decoder::new(I2C_ADDRESS_DDC_CI_MDCP29XX);
+    let full = reply(&[0xe3, 0x00, 0x00, b'(', b'p']);
+    for index in 1..full.len() {
+        let mut corrupted = full.clone();
+        corrupted[index] ^= 0x01;
+        let result = decoder.decode(&mut corrupted);
+        assert!(result.is_err(), "corrupted byte {} was accepted", index);
+    }
+}
+
+#[test]
+fn test_decode_every_length() {
+    for address in [I2C_ADDRESS_DDC_CI, I2C_ADDRESS_DDC_CI_MDCP29XX] {
+        let decoder = Decoder::new(address);
+        for len in 0..=MAX_DATA_LEN {
+            let data: Vec<u8> = (0..len as u8).map(|i| i.wrapping_mul(59).wrapping_add(1)).collect();
+            let full = reply(&data);
+            assert_eq!(full[1], 0x80 + len as u8);
+            let mut response = full.clone();
+            let result = decoder.decode(&mut response);
+            if len == 0 {
+                assert!(matches!(result, Err(Error::MonitorBusy)));
+            } else {
+                assert_eq!(result.unwrap(), data.as_slice(), "length byte {:#04x}", full[1]);
+            }
+            // Without its checksum, the reply is too short for its length byte
+            assert!(matches!(
+                decoder.decode(&mut full[..full.len() - 1].to_vec()),
+                Err(Error::Ddc(ErrorCode::InvalidLength))
+            ));
+        }
+    }
+}
+
+#[test]
+fn test_decode_every_single_bit_error() {
+    for address in [I2C_ADDRESS_DDC_CI, I2C_ADDRESS_DDC_CI_MDCP29XX] {
+        let decoder = Decoder::new(address);
+        for len in 0..=MAX_DATA_LEN {
+            let data: Vec<u8> = (0..len as u8).map(|i| i.wrapping_mul(59).wrapping_add(1)).collect();
+            let full = reply(&data);
+            for index in 1..full.len() {
+                for bit in 0..8 {
+                    let mut corrupted = full.clone();
+                    corrupted[index] ^= 1 << bit;
+                    let result = decoder.decode(&mut corrupted);
+                    // A corrupted length byte may also point past the end of the reply
+                    let length_error = index == 1 && matches!(result, Err(Error::Ddc(ErrorCode::InvalidLength)));
+                    assert!(
+                        length_error || matches!(result, Err(Error::Ddc(ErrorCode::InvalidChecksum))),
+                        "length {}, byte {}, bit {}: {:?}",
+                        len,
+                        index,
+                        bit,
+                        result
+                    );
+                }
+            }
+        }
+    }
+}
+
+#[test]
+fn test_decode_mdcp29xx_source_address_mismatch() {
+    let decoder = Decoder::new(I2C_ADDRESS_DDC_CI_MDCP29XX);
+    let full = reply(&[0x02, 0x00, 0x10, 0x00, 0x00, 0x64, 0x00, 0x32]);
+    // The source address is not covered by the checksum, so every other address is reported as a mismatch
+    for actual in (0..=0xff).filter(|&actual| actual != 0x6e) {
+        let mut response = full.clone();
+        response[0] = actual;
+        assert!(matches!(
+            decoder.decode(&mut response),
+            Err(Error::SourceAddressMismatch { expected: 0x6e, actual: a }) if a == actual
+        ));
+    }
+}
+
+#[test]
+fn test_encode_decode_share_address() {
+    let encoder = Encoder::new(I2C_ADDRESS_DDC_CI_MDCP29XX);
+    assert_eq!(encoder.i2c_address(), I2C_ADDRESS_DDC_CI_MDCP29XX);
+    assert_eq!(encoder, Encoder::new(0xb7));
+    assert_ne!(encoder, Encoder::new(I2C_ADDRESS_DDC_CI));
+}
+
+#[test]
+fn test_decode_source_address_mismatch() {
+    let mut response = reply(&[0x02, 0x00, 0x10, 0x00, 0x00, 0x64, 0x00, 0x32]);
+    response[0] = 0x6c;
+    assert!(matches!(
+        Decoder::new(I2C_ADDRESS_DDC_CI).decode(&mut response),
+        Err(Error::SourceAddressMismatch {
+            expected: 0x6e,
+            actual: 0x6c
+        })
+    ));
+}
+
+#[test]
+fn test_reply_opcode() {
+    assert_eq!(reply_opcode(&[0x01, 0x10]), Some(0x02));
+    assert_eq!(reply_opcode(&[0x07]), Some(0x4e));
+    assert_eq!(reply_opcode(&[0xe2, 0x73, 0x00, 0x00]), Some(0xe4));
+    assert_eq!(reply_opcode(&[0xf3, 0x00, 0x00]), Some(0xe3));
+    assert_eq!(reply_opcode(&[0x03, 0x10, 0x00, 0x32]), None);
+    assert_eq!(reply_opcode(&[0x0c]), None);
+    assert_eq!(reply_opcode(&[]), None);
+}
+
+#[test]
+fn test_decode_reply_opcode() {
+    let decoder = Decoder::new(I2C_ADDRESS_DDC_CI);
+    let vcp_reply = reply(&[0x02, 0x00, 0x10, 0x00, 0x00, 0x64, 0x00, 0x32]);
+    assert!(decoder.decode_reply(&[0x01, 0x10], &mut vcp_reply.clone()).is_ok());
+    // A stale Get VCP Feature reply must not be taken for a capabilities fragment
+    assert!(matches!(
+        decoder.decode_reply(&[0xf3, 0x00, 0x00], &mut vcp_reply.clone()),
+        Err(Error::OpcodeMismatch {
+            expected: 0xe3,
+            actual: 0x02
+        })
+    ));
+    // Commands without replies are not checked
+    assert!(decoder.decode_reply(&[0x0c], &mut vcp_reply.clone()).is_ok());
+}
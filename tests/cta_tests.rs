@@ -0,0 +1,147 @@
+extern crate ddc_macos;
+
+use ddc_macos::edid::{AudioFormat, CtaExtension, DataBlock, Edid, ShortVideoDescriptor, HDMI_FORUM_OUI, HDMI_OUI};
+
+const DELL_EDID: &[u8] = include_bytes!("edid/dell.edid.bin");
+
+/// Builds a CTA-861 extension block out of data blocks, without detailed timings
+fn cta_block(flags: u8, data_blocks: &[&[u8]]) -> Vec<u8> {
+    let mut block = vec![0x02, 0x03, 0x00, flags];
+    for data_block in data_blocks {
+        block.extend_from_slice(data_block);
+    }
+    block[2] = block.len() as u8;
+    block.resize(128, 0);
+    let checksum = block.iter().fold(0u8, |sum, byte| sum.wrapping_add(*byte));
+    block[127] = 0u8.wrapping_sub(checksum);
+    block
+}
+
+#[test]
+fn test_parse_dell_cta() {
+    let edid = Edid::parse(DELL_EDID).unwrap();
+    let extensions = edid.cta_extensions();
+    assert_eq!(extensions.len(), 1);
+    let cta = &extensions[0];
+    assert_eq!(cta.revision, 3);
+    assert!(cta.underscan && cta.basic_audio && cta.ycbcr444 && cta.ycbcr422);
+    assert_eq!(cta.native_detailed_timings, 1);
+
+    let audio: Vec<_> = cta.audio_descriptors().collect();
+    assert_eq!(audio.len(), 1);
+    assert_eq!(audio[0].format, AudioFormat::Lpcm);
+    assert_eq!(audio[0].channels, 2);
+    assert_eq!(audio[0].sample_rates, vec![32_000, 44_100, 48_000]);
+    assert_eq!(audio[0].bit_depths, vec![16]);
+
+    assert_eq!(cta.speaker_allocation().unwrap().speakers(), vec!["FL/FR"]);
+    assert!(cta
+        .data_blocks
+        .iter()
+        .any(|block| matches!(block, DataBlock::VendorSpecific(0x00044b, _))));
+
+    let colorimetry = cta.colorimetry().unwrap();
+    assert!(colorimetry.bt2020_rgb && colorimetry.bt2020_ycc);
+    assert!(!colorimetry.xvycc709);
+
+    let hdr = cta.hdr_static_metadata().unwrap();
+    assert!(hdr.sdr && hdr.pq);
+    assert!(!hdr.hlg && !hdr.traditional_hdr);
+    assert!(hdr.static_metadata_type1);
+    assert!((hdr.max_luminance.unwrap() - 1060.0).abs() < 1.0);
+
+    assert_eq!(cta.detailed_timings.len(), 1);
+    assert_eq!(cta.detailed_timings[0].horizontal_active, 3440);
+    assert!((cta.detailed_timings[0].refresh_rate() - 99.98).abs() < 0.01);
+}
+
+#[test]
+fn test_parse_video_blocks() {
+    let block = cta_block(
+        0x00,
+        &[
+            // Video: native 1080p60 (VIC 16), 720p60 (VIC 4), 4K60 (VIC 97)
+            &[0x43, 0x90, 0x04, 0x61],
+            // YCbCr 4:2:0 video: 4K120 (VIC 118)
+            &[0xe2, 0x0e, 0x76],
+            // YCbCr 4:2:0 capability map: third SVD
+            &[0xe2, 0x0f, 0x04],
+        ],
+    );
+    let cta = CtaExtension::parse(&block).unwrap();
+    assert_eq!(
+        cta.video_descriptors().copied().collect::<Vec<_>>(),
+        vec![
+            ShortVideoDescriptor { vic: 16, native: true },
+            ShortVideoDescriptor { vic: 4, native: false },
+            ShortVideoDescriptor { vic: 97, native: false },
+        ]
+    );
+    assert_eq!(cta.ycbcr420_vics(), vec![118, 97]);
+    assert!(cta.detailed_timings.is_empty());
+}
+
+#[test]
+fn test_parse_hdmi_blocks() {
+    let block = cta_block(
+        0x40,
+        &[
+            // HDMI 1.4: physical address 1.0.0.0, deep color 36/30 bits, 300 MHz
+            &[0x67, 0x03, 0x0c, 0x00, 0x10, 0x00, 0x38, 0x3c],
+            // HDMI Forum: version 1, 600 MHz, SCDC present, FRL 3, 4:2:0 deep color 30 bits
+            &[0x67, 0xd8, 0x5d, 0xc4, 0x01, 0x78, 0x80, 0x31],
+            // Enhanced AC-3, 6 channels, 48 kHz
+            &[0x23, 0x55, 0x04, 0x00],
+        ],
+    );
+    let cta = CtaExtension::parse(&block).unwrap();
+    let hdmi = cta
+        .data_blocks
+        .iter()
+        .find_map(|block| match block {
+            DataBlock::Hdmi(hdmi) => Some(hdmi),
+            _ => None,
+        })
+        .unwrap();
+    assert_eq!(hdmi.physical_address, 0x1000);
+    assert!(hdmi.deep_color_36 && hdmi.deep_color_30 && hdmi.deep_color_ycbcr444);
+    assert!(!hdmi.deep_color_48);
+    assert_eq!(hdmi.max_tmds_clock_mhz, Some(300));
+
+    let forum = cta
+        .data_blocks
+        .iter()
+        .find_map(|block| match block {
+            DataBlock::HdmiForum(forum) => Some(forum),
+            _ => None,
+        })
+        .unwrap();
+    assert_eq!(forum.version, 1);
+    assert_eq!(forum.max_tmds_character_rate_mhz, Some(600));
+    assert!(forum.scdc_present);
+    assert_eq!(forum.max_frl_rate, 3);
+    assert!(forum.deep_color_420_30 && !forum.deep_color_420_36);
+
+    let audio: Vec<_> = cta.audio_descriptors().collect();
+    assert_eq!(audio[0].format, AudioFormat::EnhancedAc3);
+    assert_eq!(audio[0].channels, 6);
+    assert_eq!(audio[0].sample_rates, vec![48_000]);
+    assert!(audio[0].bit_depths.is_empty());
+    assert_eq!((HDMI_OUI, HDMI_FORUM_OUI), (0x000c03, 0xc45dd8));
+}
+
+#[test]
+fn test_parse_not_cta() {
+    let edid = Edid::parse(DELL_EDID).unwrap();
+    assert!(CtaExtension::parse(&edid.extensions[1]).is_none());
+    assert!(CtaExtension::parse(&[0x02, 0x03]).is_none());
+}
+
+#[test]
+fn test_parse_truncated_data_block() {
+    // The last data block claims more bytes than there are before the detailed timings
+    let mut block = cta_block(0x00, &[&[0x42, 0x10, 0x04], &[0x45, 0x01]]);
+    block[2] = 9;
+    let cta = CtaExtension::parse(&block).unwrap();
+    assert_eq!(cta.data_blocks.len(), 1);
+}
@@ -0,0 +1,156 @@
+extern crate ddc_macos;
+
+use ddc_macos::edid::{DisplayIdBlock, DisplayIdExtension, Edid};
+
+const DELL_EDID: &[u8] = include_bytes!("edid/dell.edid.bin");
+
+/// Builds a DisplayID extension block out of data blocks
+fn displayid_block(version: u8, data_blocks: &[&[u8]]) -> Vec<u8> {
+    let mut block = vec![0x70, version, 0x00, 0x00, 0x00];
+    for data_block in data_blocks {
+        block.extend_from_slice(data_block);
+    }
+    block[2] = (block.len() - 5) as u8;
+    block.resize(128, 0);
+    let checksum = block.iter().fold(0u8, |sum, byte| sum.wrapping_add(*byte));
+    block[127] = 0u8.wrapping_sub(checksum);
+    block
+}
+
+#[test]
+fn test_parse_dell_displayid() {
+    let edid = Edid::parse(DELL_EDID).unwrap();
+    let extensions = edid.displayid_extensions();
+    assert_eq!(extensions.len(), 1);
+    let displayid = &extensions[0];
+    assert_eq!((displayid.version, displayid.revision), (1, 2));
+    assert_eq!(displayid.product_type, 3);
+
+    let timings: Vec<_> = displayid.detailed_timings().collect();
+    assert_eq!(timings.len(), 3);
+    for timing in &timings {
+        assert_eq!((timing.horizontal_active, timing.vertical_active), (3440, 1440));
+    }
+    assert_eq!(timings[0].pixel_clock_khz, 658_750);
+    assert_eq!(timings[0].horizontal_blanking, 160);
+    assert_eq!(timings[0].horizontal_front_porch, 48);
+    assert!(timings[0].horizontal_sync_positive);
+    assert_eq!(timings[0].vertical_blanking, 85);
+    assert!(!timings[0].vertical_sync_positive);
+    let rates: Vec<_> = timings
+        .iter()
+        .map(|timing| timing.refresh_rate().round() as u32)
+        .collect();
+    assert_eq!(rates, [120, 144, 175]);
+    assert_eq!(displayid.native_resolution(), Some((3440, 1440)));
+    assert!(displayid.tiled_display_topology().is_none());
+}
+
+#[test]
+fn test_not_displayid() {
+    let edid = Edid::parse(DELL_EDID).unwrap();
+    assert!(DisplayIdExtension::parse(&edid.extensions[0]).is_none());
+    assert!(DisplayIdExtension::parse(&[0x70; 16]).is_none());
+}
+
+#[test]
+fn test_product_identification_v1() {
+    let mut product = vec![
+        0x00, 0x00, 17, b'A', b'B', b'C', 0x34, 0x12, 0x78, 0x56, 0x34, 0x12, 10, 24, 5,
+    ];
+    product.extend_from_slice(b"Panel");
+    let displayid = DisplayIdExtension::parse(&displayid_block(0x13, &[&product])).unwrap();
+    let product = displayid.product_identification().unwrap();
+    assert_eq!(product.manufacturer, "ABC");
+    assert_eq!(product.product_code, 0x1234);
+    assert_eq!(product.serial_number, 0x12345678);
+    assert_eq!((product.week, product.year), (10, 2024));
+    assert_eq!(product.product_name, "Panel");
+}
+
+#[test]
+fn test_display_parameters_v2() {
+    let mut parameters = vec![0x21, 0x00, 29];
+    parameters.extend_from_slice(&6000u16.to_le_bytes());
+    parameters.extend_from_slice(&3400u16.to_le_bytes());
+    parameters.extend_from_slice(&3840u16.to_le_bytes());
+    parameters.extend_from_slice(&2160u16.to_le_bytes());
+    parameters.extend_from_slice(&[0; 13]);
+    // 600 and 0.5 cd/m² as half-precision floats
+    parameters.extend_from_slice(&[0xb0, 0x60, 0xb0, 0x60, 0x00, 0x38, 0x00, 120]);
+    let displayid = DisplayIdExtension::parse(&displayid_block(0x20, &[&parameters])).unwrap();
+    assert_eq!(displayid.version, 2);
+    let parameters = displayid.display_parameters().unwrap();
+    assert_eq!(parameters.horizontal_image_size_mm, 600.0);
+    assert_eq!(parameters.vertical_image_size_mm, 340.0);
+    assert_eq!((parameters.horizontal_pixels, parameters.vertical_pixels), (3840, 2160));
+    assert_eq!(parameters.max_luminance, Some(600.0));
+    assert_eq!(parameters.min_luminance, Some(0.5));
+    assert_eq!(parameters.gamma, Some(2.2));
+    assert_eq!(displayid.native_resolution(), Some((3840, 2160)));
+}
+
+#[test]
+fn test_type_vii_timing() {
+    let mut timing = vec![0x22, 0x00, 20];
+    // 594 MHz, preferred, 3840x2160 with 560x90 blanking: 60 Hz
+    timing.extend_from_slice(&(594_000u32 - 1).to_le_bytes()[..3]);
+    timing.push(0x84);
+    for value in [3840u16, 560, 176 | 0x8000, 88, 2160, 90, 8 | 0x8000, 10] {
+        timing.extend_from_slice(&(value - 1).to_le_bytes());
+    }
+    let displayid = DisplayIdExtension::parse(&displayid_block(0x20, &[&timing])).unwrap();
+    let timing = displayid.detailed_timings().next().unwrap();
+    assert!(timing.preferred);
+    assert_eq!(timing.pixel_clock_khz, 594_000);
+    assert_eq!(timing.horizontal_front_porch, 176);
+    assert!(timing.horizontal_sync_positive && timing.vertical_sync_positive);
+    assert_eq!(timing.refresh_rate(), 60.0);
+}
+
+#[test]
+fn test_tiled_display_topology() {
+    let mut tile = vec![0x28, 0x00, 22, 0x80, 0x10, 0x10, 0x00];
+    tile.extend_from_slice(&2559u16.to_le_bytes());
+    tile.extend_from_slice(&2879u16.to_le_bytes());
+    tile.extend_from_slice(&[0, 0, 0, 0, 0, 0x00, 0x04, 0x4b, 0x01, 0x02, 0x04, 0x03, 0x02, 0x01]);
+    let unknown = [0x7e, 0x00, 0x02, 0xaa, 0xbb];
+    let displayid = DisplayIdExtension::parse(&displayid_block(0x20, &[&tile, &unknown])).unwrap();
+    let topology = displayid.tiled_display_topology().unwrap();
+    assert!(topology.single_enclosure);
+    assert_eq!((topology.horizontal_tiles, topology.vertical_tiles), (2, 1));
+    assert_eq!((topology.horizontal_location, topology.vertical_location), (1, 0));
+    assert_eq!(topology.total_resolution(), (5120, 2880));
+    assert_eq!(topology.manufacturer, "00044B");
+    assert_eq!(topology.product_code, 0x0201);
+    assert_eq!(topology.serial_number, 0x01020304);
+    assert_eq!(
+        displayid.data_blocks[1],
+        DisplayIdBlock::Other(0x7e, 0x00, vec![0xaa, 0xbb])
+    );
+}
+
+#[test]
+fn test_largest_sizes() {
+    let mut timing = vec![0x22, 0x00, 20, 0xff, 0xff, 0xff, 0x00];
+    timing.extend_from_slice(&[0xff; 16]);
+    let mut tile = vec![0x28, 0x00, 22, 0x00, 0x00, 0x00, 0x00];
+    tile.extend_from_slice(&[0xff; 4]);
+    tile.extend_from_slice(&[0; 14]);
+    let displayid = DisplayIdExtension::parse(&displayid_block(0x20, &[&timing, &tile])).unwrap();
+    let timing = displayid.detailed_timings().next().unwrap();
+    assert_eq!((timing.horizontal_active, timing.vertical_active), (65536, 65536));
+    assert_eq!((timing.horizontal_blanking, timing.vertical_blanking), (65536, 65536));
+    assert_eq!(
+        (timing.horizontal_sync_width, timing.vertical_sync_width),
+        (65536, 65536)
+    );
+    assert_eq!(
+        (timing.horizontal_front_porch, timing.vertical_front_porch),
+        (32768, 32768)
+    );
+    assert_eq!(displayid.native_resolution(), Some((65536, 65536)));
+    let topology = displayid.tiled_display_topology().unwrap();
+    assert_eq!((topology.tile_width, topology.tile_height), (65536, 65536));
+    assert_eq!(topology.total_resolution(), (65536, 65536));
+}
@@ -0,0 +1,116 @@
+extern crate ddc_macos;
+
+use ddc_macos::edid::{
+    Descriptor, DigitalInterface, Edid, EdidError, ManufactureDate, PhysicalSize, StandardTiming, VideoInput,
+};
+
+const DELL_EDID: &[u8] = include_bytes!("edid/dell.edid.bin");
+
+#[test]
+fn test_parse_identification() {
+    let edid = Edid::parse(DELL_EDID).unwrap();
+    assert_eq!(edid.manufacturer, "DEL");
+    assert_eq!(edid.product_code, 0xa1e4);
+    assert_eq!(edid.serial_number, 0x3132_4653);
+    assert_eq!(
+        edid.manufacture_date,
+        ManufactureDate::Manufactured {
+            week: Some(33),
+            year: 2022
+        }
+    );
+    assert_eq!((edid.version, edid.revision), (1, 4));
+    assert_eq!(edid.product_name(), Some("Dell AW3423DW"));
+    assert_eq!(edid.serial_string(), Some("#G7QYMxgwABxd"));
+}
+
+#[test]
+fn test_parse_display_parameters() {
+    let edid = Edid::parse(DELL_EDID).unwrap();
+    assert_eq!(
+        edid.video_input,
+        VideoInput::Digital {
+            bit_depth: Some(10),
+            interface: DigitalInterface::DisplayPort
+        }
+    );
+    assert_eq!(
+        edid.physical_size,
+        Some(PhysicalSize::Dimensions {
+            width_cm: 81,
+            height_cm: 35
+        })
+    );
+    assert_eq!(edid.gamma, Some(2.2));
+    assert!(!edid.features.standby);
+    assert!(edid.features.active_off);
+    assert!(!edid.features.srgb_default);
+    assert!(edid.features.preferred_timing_native);
+    assert!(edid.features.continuous_frequency);
+    assert!((edid.chromaticity.white.x - 0.3125).abs() < 0.001);
+    assert!((edid.chromaticity.white.y - 0.3291).abs() < 0.001);
+    assert!(edid.chromaticity.red.x > 0.6);
+}
+
+#[test]
+fn test_parse_timings() {
+    let edid = Edid::parse(DELL_EDID).unwrap();
+    let timing = |width, height, refresh_rate| StandardTiming {
+        width,
+        height,
+        refresh_rate,
+    };
+    assert_eq!(
+        edid.established_timings,
+        vec![timing(640, 480, 60), timing(800, 600, 60), timing(1024, 768, 60)]
+    );
+    assert!(edid.standard_timings.is_empty());
+
+    let preferred = edid.detailed_timings().next().unwrap();
+    assert_eq!(preferred.pixel_clock_khz, 319_750);
+    assert_eq!((preferred.horizontal_active, preferred.vertical_active), (3440, 1440));
+    assert_eq!((preferred.horizontal_blanking, preferred.vertical_blanking), (160, 41));
+    assert_eq!(
+        (preferred.horizontal_image_size_mm, preferred.vertical_image_size_mm),
+        (809, 354)
+    );
+    assert!(!preferred.interlaced);
+    assert!((preferred.refresh_rate() - 59.97).abs() < 0.01);
+}
+
+#[test]
+fn test_parse_descriptors() {
+    let edid = Edid::parse(DELL_EDID).unwrap();
+    assert_eq!(edid.descriptors.len(), 4);
+    assert!(matches!(edid.descriptors[0], Descriptor::DetailedTiming(_)));
+    let limits = edid.range_limits().unwrap();
+    assert_eq!((limits.min_vertical_hz, limits.max_vertical_hz), (1, 175));
+}
+
+#[test]
+fn test_parse_extensions() {
+    let edid = Edid::parse(DELL_EDID).unwrap();
+    assert_eq!(edid.extension_count, 2);
+    assert_eq!(edid.extensions.len(), 2);
+    assert_eq!(edid.extensions[0][0], 0x02);
+    assert_eq!(edid.extensions[1][0], 0x70);
+    assert!(Edid::parse(&DELL_EDID[..128]).unwrap().extensions.is_empty());
+}
+
+#[test]
+fn test_parse_errors() {
+    assert_eq!(Edid::parse(&DELL_EDID[..100]), Err(EdidError::TooShort(100)));
+    let mut corrupted = DELL_EDID.to_vec();
+    corrupted[0] = 0x01;
+    assert_eq!(Edid::parse(&corrupted), Err(EdidError::InvalidHeader));
+}
+
+#[test]
+fn test_standard_timing_display() {
+    let timing = StandardTiming {
+        width: 1920,
+        height: 1080,
+        refresh_rate: 60,
+    };
+    assert_eq!(timing.to_string(), "1920x1080@60");
+}
@@ -0,0 +1,136 @@
+extern crate ddc_macos;
+
+use ddc::{Ddc, ErrorCode, I2C_ADDRESS_DDC_CI};
+use ddc_macos::codec::Encoder;
+use ddc_macos::transport::{DdcTransport, Fault, FaultInjector, SimulatedMonitor};
+use ddc_macos::{Error, Monitor};
+use std::time::Duration;
+
+fn monitor_with_faults(script: Vec<Option<Fault>>) -> Monitor {
+    Monitor::with_transport(
+        FaultInjector::scripted(SimulatedMonitor::new(), script),
+        I2C_ADDRESS_DDC_CI,
+    )
+}
+
+#[test]
+fn test_bad_checksum() {
+    let mut monitor = monitor_with_faults(vec![Some(Fault::BadChecksum)]);
+    assert!(matches!(
+        monitor.get_vcp_feature(0x10),
+        Err(Error::Ddc(ErrorCode::InvalidChecksum))
+    ));
+    assert_eq!(monitor.get_vcp_feature(0x10).unwrap().value(), 50);
+}
+
+#[test]
+fn test_truncated_reply() {
+    let mut monitor = monitor_with_faults(vec![Some(Fault::Truncate(5))]);
+    assert!(matches!(
+        monitor.get_vcp_feature(0x10),
+        Err(Error::Ddc(ErrorCode::InvalidLength))
+    ));
+}
+
+#[test]
+fn test_zeroed_reply() {
+    let mut monitor = monitor_with_faults(vec![Some(Fault::ZeroedReply)]);
+    assert!(matches!(
+        monitor.get_vcp_feature(0x10),
+        Err(Error::Ddc(ErrorCode::InvalidChecksum))
+    ));
+}
+
+#[test]
+fn test_null_message() {
+    let mut monitor = monitor_with_faults(vec![Some(Fault::NullMessage)]);
+    assert!(matches!(monitor.get_vcp_feature(0x10), Err(Error::MonitorBusy)));
+}
+
+#[test]
+fn test_io_error() {
+    let mut monitor = monitor_with_faults(vec![None, Some(Fault::Io(0xe00002bc_u32 as i32))]);
+    assert!(monitor.get_vcp_feature(0x10).is_ok());
+    assert!(matches!(monitor.get_vcp_feature(0x10), Err(Error::Io(code)) if code == 0xe00002bc_u32 as i32));
+}
+
+#[test]
+fn test_write_faults_are_ignored() {
+    let simulated = SimulatedMonitor::new();
+    let mut monitor = Monitor::with_transport(
+        FaultInjector::scripted(simulated.clone(), vec![Some(Fault::BadChecksum)]),
+        I2C_ADDRESS_DDC_CI,
+    );
+    monitor.set_vcp_feature(0x10, 10).unwrap();
+    assert_eq!(simulated.feature(0x10).unwrap().value(), 10);
+}
+
+#[test]
+fn test_io_error_on_write() {
+    let simulated = SimulatedMonitor::new();
+    let mut monitor = Monitor::with_transport(
+        FaultInjector::scripted(simulated.clone(), vec![Some(Fault::Io(-5))]),
+        I2C_ADDRESS_DDC_CI,
+    );
+    assert!(matches!(monitor.set_vcp_feature(0x10, 10), Err(Error::Io(-5))));
+    monitor.set_vcp_feature(0x10, 20).unwrap();
+    assert_eq!(simulated.feature(0x10).unwrap().value(), 20);
+}
+
+#[test]
+fn test_injected_count() {
+    let mut injector = FaultInjector::scripted(
+        SimulatedMonitor::new(),
+        vec![
+            Some(Fault::BadChecksum),
+            Some(Fault::Io(-5)),
+            Some(Fault::Delay(Duration::ZERO)),
+        ],
+    );
+    // Set VCP Feature 0x10 to 10, which has no reply to corrupt
+    let mut packet = [0u8; 16];
+    let packet = Encoder::new(I2C_ADDRESS_DDC_CI).encode(&[0x03, 0x10, 0x00, 0x0a], &mut packet);
+    let mut out = [0u8; 0];
+    assert!(injector
+        .execute(I2C_ADDRESS_DDC_CI, packet, &mut out, Duration::ZERO)
+        .is_ok());
+    assert_eq!(injector.injected(), 0);
+    assert!(injector
+        .execute(I2C_ADDRESS_DDC_CI, packet, &mut out, Duration::ZERO)
+        .is_err());
+    assert_eq!(injector.injected(), 1);
+    assert!(injector
+        .execute(I2C_ADDRESS_DDC_CI, packet, &mut out, Duration::ZERO)
+        .is_ok());
+    assert_eq!(injector.injected(), 2);
+}
+
+#[test]
+fn test_random_faults_are_reproducible() {
+    let run = |seed| {
+        let mut monitor = Monitor::with_transport(
+            FaultInjector::random(
+                SimulatedMonitor::new(),
+                0.5,
+                vec![Fault::BadChecksum, Fault::ZeroedReply],
+                seed,
+            ),
+            I2C_ADDRESS_DDC_CI,
+        );
+        (0..32)
+            .map(|_| monitor.get_vcp_feature(0x10).is_ok())
+            .collect::<Vec<_>>()
+    };
+    let outcomes = run(42);
+    assert_eq!(outcomes, run(42));
+    assert!(outcomes.iter().any(|ok| *ok));
+    assert!(outcomes.iter().any(|ok| !*ok));
+}
+
+#[test]
+fn test_map_transport() {
+    let monitor = Monitor::with_transport(SimulatedMonitor::new(), I2C_ADDRESS_DDC_CI);
+    let mut monitor = monitor.map_transport(|transport| FaultInjector::scripted(transport, vec![Some(Fault::Io(-1))]));
+    assert!(matches!(monitor.get_vcp_feature(0x10), Err(Error::Io(-1))));
+    assert!(monitor.get_vcp_feature(0x10).is_ok());
+}
@@ -0,0 +1,129 @@
+extern crate ddc_macos;
+
+use ddc::I2C_ADDRESS_DDC_CI;
+use ddc_macos::transport::SimulatedMonitor;
+use ddc_macos::{
+    Backend, FilterCondition, FilterField, FilterOperator, Monitor, MonitorFilter, MonitorId, MonitorInfo,
+};
+
+const DELL_EDID: &[u8] = include_bytes!("edid/dell.edid.bin");
+
+fn dell() -> MonitorInfo {
+    MonitorInfo {
+        backend: Backend::Arm,
+        vendor: Some("DEL".to_string()),
+        product: Some(0xa1e4),
+        model: Some("DELL U2723QE".to_string()),
+        serial: Some("7QYMXG3".to_string()),
+        description: "DELL U2723QE".to_string(),
+        id: Some("DEL/a1e4/00000000/7QYMXG3/IOService:/dispext0".parse().unwrap()),
+        ..Default::default()
+    }
+}
+
+fn lg() -> MonitorInfo {
+    MonitorInfo {
+        backend: Backend::Intel,
+        vendor: Some("GSM".to_string()),
+        product: Some(0x5b09),
+        model: Some("LG ULTRAFINE".to_string()),
+        serial: None,
+        description: "LG ULTRAFINE".to_string(),
+        id: Some("GSM/5b09/00000000/".parse().unwrap()),
+        ..Default::default()
+    }
+}
+
+fn selected(selector: &str) -> Vec<usize> {
+    let filter = MonitorFilter::parse(selector).unwrap();
+    [dell(), lg()]
+        .iter()
+        .enumerate()
+        .filter(|(index, info)| filter.matches(*index, info))
+        .map(|(index, _)| index)
+        .collect()
+}
+
+#[test]
+fn test_selectors() {
+    assert_eq!(selected(""), [0, 1]);
+    assert_eq!(selected("vendor=DEL"), [0]);
+    assert_eq!(selected("vendor=del"), [0]);
+    assert_eq!(selected("manufacturer!=DEL"), [1]);
+    assert_eq!(selected("vendor=DEL,model~*U27*"), [0]);
+    assert_eq!(selected("vendor=DEL, model~LG*"), Vec::<usize>::new());
+    assert_eq!(selected("model~dell u27??qe"), [0]);
+    assert_eq!(selected("name!~DELL*"), [1]);
+    assert_eq!(selected("serial=7qymxg3"), [0]);
+    // Unknown values never equal anything
+    assert_eq!(selected("serial!=7QYMXG3"), [1]);
+    assert_eq!(selected("serial~*"), [0]);
+    assert_eq!(selected("product=5b09"), [1]);
+    assert_eq!(selected("product=0xA1E4"), [0]);
+    assert_eq!(selected("product~a1*"), [0]);
+    assert_eq!(selected("backend=arm"), [0]);
+    assert_eq!(selected("connection=Intel"), [1]);
+    assert_eq!(selected("index=1"), [1]);
+    assert_eq!(selected("index!=1"), [0]);
+    assert_eq!(selected("description~*fine"), [1]);
+}
+
+#[test]
+fn test_id_selectors() {
+    assert_eq!(selected("id=DEL/a1e4/00000000/7QYMXG3"), [0]);
+    assert_eq!(selected("id=DEL/a1e4/00000000/7QYMXG3/IOService:/dispext0"), [0]);
+    assert_eq!(
+        selected("id=DEL/a1e4/00000000/7QYMXG3/IOService:/dispext1"),
+        Vec::<usize>::new()
+    );
+    assert_eq!(selected("id~GSM/*"), [1]);
+}
+
+#[test]
+fn test_parse_errors() {
+    for invalid in [
+        "vendor",
+        "colour=red",
+        "vendor<DEL",
+        "index=first",
+        "product=xyz",
+        "backend=usb",
+        "id=DEL",
+        "vendor=DEL,,model",
+    ] {
+        assert!(MonitorFilter::parse(invalid).is_err(), "{}", invalid);
+    }
+}
+
+#[test]
+fn test_display() {
+    let filter = MonitorFilter::parse(" vendor = DEL ,model~U27*,serial!=A,backend!~i*").unwrap();
+    assert_eq!(filter.conditions().len(), 4);
+    assert_eq!(filter.to_string(), "vendor=DEL,model~U27*,serial!=A,backend!~i*");
+    assert_eq!(MonitorFilter::parse(&filter.to_string()).unwrap(), filter);
+
+    let filter = MonitorFilter::all()
+        .with_condition(FilterCondition::new(FilterField::Index, FilterOperator::Equals, "2").unwrap());
+    assert_eq!(filter.to_string(), "index=2");
+    assert!(FilterCondition::new(FilterField::Index, FilterOperator::Equals, "-1").is_err());
+}
+
+#[test]
+fn test_filter_monitors() {
+    let mut dell = Monitor::with_transport(SimulatedMonitor::new(), I2C_ADDRESS_DDC_CI);
+    dell.set_edid(DELL_EDID.to_vec());
+    let info = dell.info();
+    assert_eq!(info.backend, Backend::Custom);
+    assert_eq!(info.vendor.as_deref(), Some("DEL"));
+    assert_eq!(info.product, Some(0xa1e4));
+    assert_eq!(info.serial.as_deref(), Some("#G7QYMxgwABxd"));
+    assert_eq!(info.id, MonitorId::from_edid(DELL_EDID, None));
+    let anonymous = Monitor::with_transport(SimulatedMonitor::new(), 0x38);
+
+    let monitors = vec![anonymous, dell];
+    let monitors = Monitor::filter_in(monitors, &MonitorFilter::parse("vendor=DEL").unwrap());
+    assert_eq!(monitors.len(), 1);
+    assert_eq!(monitors[0].info().vendor.as_deref(), Some("DEL"));
+    let monitors = Monitor::filter_in(monitors, &MonitorFilter::parse("index=1").unwrap());
+    assert!(monitors.is_empty());
+}
@@ -0,0 +1,129 @@
+extern crate ddc_macos;
+
+use ddc::I2C_ADDRESS_DDC_CI;
+use ddc_macos::transport::SimulatedMonitor;
+use ddc_macos::{Monitor, MonitorId};
+
+const DELL_EDID: &[u8] = include_bytes!("edid/dell.edid.bin");
+
+fn id(serial: u32, serial_string: Option<&str>, location: Option<&str>) -> MonitorId {
+    MonitorId {
+        manufacturer: "GSM".to_string(),
+        product: 0x5b09,
+        serial,
+        serial_string: serial_string.map(str::to_string),
+        location: location.map(str::to_string),
+    }
+}
+
+/// The Dell EDID with another manufacturer ID, letters being numbered from 1
+fn edid_with_manufacturer(letters: [u16; 3]) -> Vec<u8> {
+    let mut edid = DELL_EDID.to_vec();
+    let id = letters[0] << 10 | letters[1] << 5 | letters[2];
+    edid[8..10].copy_from_slice(&id.to_be_bytes());
+    let checksum = edid[..127].iter().fold(0u8, |sum, byte| sum.wrapping_add(*byte));
+    edid[127] = 0u8.wrapping_sub(checksum);
+    edid
+}
+
+#[test]
+fn test_malformed_manufacturer_round_trip() {
+    for letters in [[0, 27, 31], [28, 29, 30]] {
+        let id = MonitorId::from_edid(&edid_with_manufacturer(letters), Some("IOService:/a/b".to_string())).unwrap();
+        assert!(!id.manufacturer.chars().all(|c| c.is_ascii_uppercase()));
+        assert_eq!(id.to_string().parse::<MonitorId>().unwrap(), id);
+    }
+    assert!("DE//a1e4/00000000/".parse::<MonitorId>().is_err());
+}
+
+#[test]
+fn test_from_edid() {
+    let id = MonitorId::from_edid(DELL_EDID, None).unwrap();
+    assert_eq!(id.manufacturer, "DEL");
+    assert_eq!(id.product, 0xa1e4);
+    assert_eq!(id.serial_string.as_deref(), Some("#G7QYMxgwABxd"));
+    assert_eq!(id.location, None);
+    assert_eq!(MonitorId::from_edid(&DELL_EDID[..64], None), None);
+}
+
+#[test]
+fn test_display_and_parse() {
+    let location = "IOService:/AppleARMPE/arm-io@10F00000/AppleT810xIO/dispext0@28200000";
+    for id in [
+        id(0, None, None),
+        id(0x1234abcd, Some("108NTRL1A123"), None),
+        id(1, Some("50%/A B"), Some(location)),
+        id(0, None, Some(location)),
+        MonitorId::from_edid(DELL_EDID, None).unwrap(),
+    ] {
+        assert_eq!(id.to_string().parse::<MonitorId>().unwrap(), id);
+    }
+    assert_eq!(id(0x1234abcd, Some("SN1"), None).to_string(), "GSM/5b09/1234abcd/SN1");
+    assert_eq!(
+        id(1, Some("a/b%"), Some("IOService:/x")).to_string(),
+        "GSM/5b09/00000001/a%2Fb%25/IOService:/x"
+    );
+    assert_eq!("GSM/5b09/00000000/".parse::<MonitorId>().unwrap(), id(0, None, None));
+}
+
+#[test]
+fn test_parse_errors() {
+    for invalid in [
+        "",
+        "GSM",
+        "GSM/5b09/00000000",
+        "GS M/5b09/00000000/",
+        "GSMX/5b09/00000000/",
+        "GSM/xyz/00000000/",
+        "GSM/5b09/100000000/",
+        "GSM/5b09/00000000/a%2",
+        "GSM/5b09/00000000/a%zz",
+    ] {
+        assert!(invalid.parse::<MonitorId>().is_err(), "{}", invalid);
+    }
+}
+
+#[test]
+fn test_position_in() {
+    let left = id(0, None, Some("left"));
+    let right = id(0, None, Some("right"));
+    let other = id(42, None, Some("left"));
+    // A single monitor with the same EDID identity is found wherever it is connected
+    assert_eq!(
+        id(42, None, Some("right")).position_in(&[left.clone(), other.clone()]),
+        Some(1)
+    );
+    assert_eq!(id(42, None, None).position_in(&[left.clone(), other.clone()]), Some(1));
+    // Identical monitors are told apart by their location
+    let candidates = [left.clone(), other, right.clone()];
+    assert_eq!(left.position_in(&candidates), Some(0));
+    assert_eq!(right.position_in(&candidates), Some(2));
+    assert_eq!(id(0, None, Some("middle")).position_in(&candidates), None);
+    assert_eq!(id(0, None, None).position_in(&candidates), None);
+    assert_eq!(id(7, None, None).position_in(&candidates), None);
+}
+
+#[test]
+fn test_find_in() {
+    let mut dell = Monitor::with_transport(SimulatedMonitor::new(), I2C_ADDRESS_DDC_CI);
+    dell.set_edid(DELL_EDID.to_vec());
+    let anonymous = Monitor::with_transport(SimulatedMonitor::new(), 0x38);
+    let id = dell.id().unwrap();
+    let found = Monitor::find_in(vec![anonymous, dell], &id).unwrap();
+    assert_eq!(found.id(), Some(id.clone()));
+    let anonymous = Monitor::with_transport(SimulatedMonitor::new(), 0x38);
+    assert!(Monitor::find_in(vec![anonymous], &id).is_none());
+}
+
+#[cfg(feature = "serde")]
+#[test]
+fn test_serde() {
+    let id = MonitorId::from_edid(DELL_EDID, Some("IOService:/display0".to_string())).unwrap();
+    let json = serde_json::to_string(&id).unwrap();
+    assert_eq!(json, format!("\"{}\"", id));
+    assert_eq!(serde_json::from_str::<MonitorId>(&json).unwrap(), id);
+    assert!(serde_json::from_str::<MonitorId>("\"DEL\"").is_err());
+    let malformed = MonitorId::from_edid(&edid_with_manufacturer([0, 27, 31]), None).unwrap();
+    let json = serde_json::to_string(&malformed).unwrap();
+    assert_eq!(serde_json::from_str::<MonitorId>(&json).unwrap(), malformed);
+}
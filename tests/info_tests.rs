@@ -0,0 +1,68 @@
+extern crate ddc_macos;
+
+use ddc::I2C_ADDRESS_DDC_CI;
+use ddc_macos::transport::SimulatedMonitor;
+use ddc_macos::{Backend, EdidSummary, Monitor, MonitorId};
+
+const DELL_EDID: &[u8] = include_bytes!("edid/dell.edid.bin");
+
+#[test]
+fn test_edid_summary() {
+    let summary = EdidSummary::parse(DELL_EDID).unwrap();
+    assert_eq!(summary.manufacturer, "DEL");
+    assert_eq!(summary.product_code, 0xa1e4);
+    assert_eq!(summary.serial_number, 0x3132_4653);
+    assert_eq!(summary.serial_string.as_deref(), Some("#G7QYMxgwABxd"));
+    assert_eq!(summary.product_name.as_deref(), Some("Dell AW3423DW"));
+    assert_eq!(summary.version, "1.4");
+    assert_eq!(summary.native_resolution, Some((3440, 1440)));
+    assert_eq!(summary.extensions, 2);
+    assert!(summary.valid);
+    assert!(EdidSummary::parse(&DELL_EDID[..64]).is_none());
+}
+
+#[test]
+fn test_monitor_info() {
+    let monitor = Monitor::with_transport(SimulatedMonitor::new(), 0x38);
+    let info = monitor.info();
+    assert_eq!(info.backend, Backend::Custom);
+    assert_eq!(info.i2c_address, 0x38);
+    assert_eq!(info.display_id, None);
+    assert!(!info.builtin);
+    assert_eq!(info.vendor, None);
+    assert_eq!(info.id, None);
+    assert_eq!(info.edid, None);
+    assert_eq!(info.edid_summary, None);
+    assert_eq!(info.description, "DDC/CI device at 0x38");
+
+    // Setting the EDID refreshes the snapshot
+    let mut monitor = Monitor::with_transport(SimulatedMonitor::new(), I2C_ADDRESS_DDC_CI);
+    monitor.set_edid(DELL_EDID.to_vec());
+    let info = monitor.info();
+    assert_eq!(info.vendor.as_deref(), Some("DEL"));
+    assert_eq!(info.model.as_deref(), Some("Dell AW3423DW"));
+    assert_eq!(info.edid.as_deref(), Some(DELL_EDID));
+    assert_eq!(info.edid_summary, EdidSummary::parse(DELL_EDID));
+    assert_eq!(info.id, MonitorId::from_edid(DELL_EDID, None));
+    assert_eq!(info.location, None);
+}
+
+#[cfg(feature = "serde")]
+#[test]
+fn test_serde() {
+    let mut monitor = Monitor::with_transport(SimulatedMonitor::new(), I2C_ADDRESS_DDC_CI);
+    monitor.set_edid(DELL_EDID.to_vec());
+    let info = monitor.info();
+    let json: serde_json::Value = serde_json::to_value(info).unwrap();
+    assert_eq!(json["backend"], "custom");
+    assert_eq!(json["i2c_address"], 0x37);
+    assert_eq!(json["vendor"], "DEL");
+    assert_eq!(json["id"], info.id.as_ref().unwrap().to_string());
+    assert_eq!(
+        json["edid_summary"]["native_resolution"],
+        serde_json::json!([3440, 1440])
+    );
+    assert_eq!(json["edid"].as_array().unwrap().len(), DELL_EDID.len());
+    let parsed: ddc_macos::MonitorInfo = serde_json::from_value(json).unwrap();
+    assert_eq!(&parsed, info);
+}
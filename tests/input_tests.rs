@@ -0,0 +1,93 @@
+extern crate ddc_macos;
+
+use ddc::I2C_ADDRESS_DDC_CI;
+use ddc_macos::transport::SimulatedMonitor;
+use ddc_macos::{Error, InputTable, Monitor};
+
+const DELL_EDID: &[u8] = include_bytes!("edid/dell.edid.bin");
+
+#[test]
+fn test_standard_names() {
+    let table = InputTable::standard();
+    assert_eq!(table.value("HDMI 1"), Some(0x11));
+    assert_eq!(table.value("hdmi-2"), Some(0x12));
+    assert_eq!(table.value("hdmi"), Some(0x11));
+    assert_eq!(table.value("dp1"), Some(0x0f));
+    assert_eq!(table.value("DisplayPort"), Some(0x0f));
+    assert_eq!(table.value("dp_2"), Some(0x10));
+    assert_eq!(table.value("vga"), Some(0x01));
+    assert_eq!(table.value("usb-c"), None);
+    assert_eq!(table.name(0x0f), Some("DisplayPort 1"));
+    assert_eq!(table.name(0x1b), None);
+}
+
+#[test]
+fn test_vendor_overrides() {
+    let dell = InputTable::for_product("DEL", 0xa1e4);
+    assert_eq!(dell.value("usb-c"), Some(0x1b));
+    assert_eq!(dell.value("USB C"), Some(0x1b));
+    assert_eq!(dell.value("dp1"), Some(0x0f));
+    assert_eq!(dell.name(0x1b), Some("USB-C"));
+
+    // LG selects its inputs through a vendor specific VCP code, not Input Select
+    assert_eq!(InputTable::for_product("GSM", 0x5b7f), InputTable::standard());
+    assert_eq!(InputTable::for_product("XYZ", 1), InputTable::standard());
+    assert_eq!(InputTable::for_edid(DELL_EDID), dell);
+    assert_eq!(InputTable::for_edid(&[0; 10]), InputTable::standard());
+}
+
+#[test]
+fn test_custom_inputs() {
+    let table = InputTable::standard()
+        .with_input("Thunderbolt", 0x19)
+        .with_input("HDMI 1", 0x90)
+        .with_alias("Thunderbolt", "tb");
+    assert_eq!(table.value("tb"), Some(0x19));
+    assert_eq!(table.value("hdmi"), Some(0x90));
+    assert_eq!(table.entries().len(), 19);
+}
+
+#[test]
+fn test_monitor_select_input() {
+    // A Dell with a USB-C input, beyond the highest MCCS input value
+    let simulated = SimulatedMonitor::new().with_feature(0x60, 0x0f, 0x1b);
+    let mut monitor = Monitor::with_transport(simulated.clone(), I2C_ADDRESS_DDC_CI);
+    monitor.set_edid(DELL_EDID.to_vec());
+
+    assert_eq!(monitor.input_name().unwrap(), "DisplayPort 1");
+    monitor.select_input("usb-c").unwrap();
+    assert_eq!(simulated.feature(0x60).unwrap().value(), 0x1b);
+    assert_eq!(monitor.input_name().unwrap(), "USB-C");
+    monitor.select_input("HDMI 2").unwrap();
+    assert_eq!(simulated.feature(0x60).unwrap().value(), 0x12);
+
+    assert!(matches!(monitor.select_input("floppy"), Err(Error::Ddc(_))));
+}
+
+/// The Dell EDID with the manufacturer ID and product code of an LG monitor
+fn lg_edid() -> Vec<u8> {
+    let mut edid = DELL_EDID.to_vec();
+    edid[8..12].copy_from_slice(&[0x1e, 0x6d, 0x7f, 0x5b]);
+    let checksum = edid[..127].iter().fold(0u8, |sum, byte| sum.wrapping_add(*byte));
+    edid[127] = 0u8.wrapping_sub(checksum);
+    edid
+}
+
+#[test]
+fn test_monitor_reports_standard_value() {
+    let simulated = SimulatedMonitor::new().with_feature(0x60, 0x11, 0xff);
+    let mut monitor = Monitor::with_transport(simulated, I2C_ADDRESS_DDC_CI);
+    monitor.set_edid(lg_edid());
+    assert_eq!(monitor.info().vendor.as_deref(), Some("GSM"));
+    assert_eq!(monitor.input_name().unwrap(), "HDMI 1");
+}
+
+#[test]
+fn test_monitor_without_edid_uses_standard_inputs() {
+    let simulated = SimulatedMonitor::new();
+    let mut monitor = Monitor::with_transport(simulated.clone(), I2C_ADDRESS_DDC_CI);
+    assert_eq!(monitor.input_table(), InputTable::standard());
+    assert!(monitor.select_input("usb-c").is_err());
+    monitor.select_input("dp2").unwrap();
+    assert_eq!(simulated.feature(0x60).unwrap().value(), 0x10);
+}
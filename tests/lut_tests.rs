@@ -0,0 +1,129 @@
+extern crate ddc_macos;
+
+use ddc::I2C_ADDRESS_DDC_CI;
+use ddc_macos::transport::{Fault, FaultInjector, SimulatedMonitor};
+use ddc_macos::{Error, GammaLut, LutChannel, LutMode, LutSize, Monitor};
+
+const BLOCK_LUT_CAPABILITIES: &str = "(prot(monitor)type(lcd)model(SIM1)cmds(01 02 03 E2 E7 F3)vcp(10 12 73 74 75))";
+
+#[test]
+fn test_lut_size() {
+    let size = LutSize::new([256, 256, 1024], [8, 8, 10]).unwrap();
+    assert_eq!(size.entries(LutChannel::Blue), 1024);
+    assert_eq!(size.bits(LutChannel::Green), 8);
+    assert_eq!(size.maximum(LutChannel::Red), 255);
+    assert_eq!(size.maximum(LutChannel::Blue), 1023);
+    assert_eq!(LutSize::parse(&size.to_bytes()).unwrap(), size);
+    assert!(LutSize::parse(&[0x01, 0x00]).is_err());
+    assert!(LutSize::uniform(1, 8).is_err());
+    assert!(LutSize::uniform(256, 17).is_err());
+    assert_eq!(LutSize::uniform(2, 16).unwrap().maximum(LutChannel::Red), 0xffff);
+}
+
+#[test]
+fn test_channel_numbers() {
+    for channel in LutChannel::ALL {
+        assert_eq!(LutChannel::from_number(channel.number()), Some(channel));
+    }
+    assert_eq!(LutChannel::from_number(0), None);
+    assert_eq!(LutChannel::from_number(4), None);
+}
+
+#[test]
+fn test_build_luts() {
+    let size = LutSize::uniform(5, 8).unwrap();
+    assert_eq!(
+        GammaLut::identity(size).channel(LutChannel::Red),
+        [0, 64, 128, 191, 255]
+    );
+    assert_eq!(
+        GammaLut::power(size, 2.0).channel(LutChannel::Green),
+        [0, 16, 64, 143, 255]
+    );
+
+    let lut = GammaLut::from_curves(size, &[0.0, 1.0], &[1.0, 0.0], &[0.0, 0.5, 0.5, 1.0]).unwrap();
+    assert_eq!(lut.channel(LutChannel::Red), [0, 64, 128, 191, 255]);
+    assert_eq!(lut.channel(LutChannel::Green), [255, 191, 128, 64, 0]);
+    assert_eq!(lut.channel(LutChannel::Blue), [0, 96, 128, 159, 255]);
+    assert!(GammaLut::from_curves(size, &[0.0], &[0.0, 1.0], &[0.0, 1.0]).is_err());
+
+    // Out of range outputs are clamped
+    let lut = GammaLut::from_fn(size, |_, input| input * 2.0 - 0.5);
+    assert_eq!(lut.channel(LutChannel::Blue), [0, 0, 128, 255, 255]);
+}
+
+#[test]
+fn test_from_values() {
+    let size = LutSize::uniform(2, 8).unwrap();
+    assert!(GammaLut::from_values(size, [vec![0, 255], vec![0, 255], vec![0, 255]]).is_ok());
+    assert!(GammaLut::from_values(size, [vec![0, 255], vec![0, 256], vec![0, 255]]).is_err());
+    assert!(GammaLut::from_values(size, [vec![0, 255], vec![0], vec![0, 255]]).is_err());
+}
+
+#[test]
+fn test_resample() {
+    let lut = GammaLut::power(LutSize::uniform(256, 8).unwrap(), 2.2);
+    let resampled = lut.resample(LutSize::uniform(1024, 10).unwrap());
+    assert_eq!(resampled.channel(LutChannel::Red).len(), 1024);
+    assert_eq!(resampled.channel(LutChannel::Red)[0], 0);
+    assert_eq!(resampled.channel(LutChannel::Red)[1023], 1023);
+    for input in [0.1, 0.5, 0.9] {
+        assert!((resampled.evaluate(LutChannel::Red, input) - lut.evaluate(LutChannel::Red, input)).abs() < 0.005);
+    }
+    assert_eq!(lut.resample(lut.size()), lut);
+}
+
+#[test]
+fn test_upload_single_point() {
+    let size = LutSize::new([8, 8, 4], [10, 10, 8]).unwrap();
+    let simulated = SimulatedMonitor::new().with_lut(size);
+    let mut monitor = Monitor::with_transport(simulated.clone(), I2C_ADDRESS_DDC_CI);
+    assert_eq!(monitor.lut_size().unwrap(), size);
+    let mode = monitor.lut_mode().unwrap();
+    assert_eq!(mode, LutMode::SinglePoint);
+
+    let lut = GammaLut::power(LutSize::uniform(16, 16).unwrap(), 2.2);
+    assert!(!monitor.verify_lut(&lut, mode).unwrap());
+    monitor.upload_lut(&lut, mode).unwrap();
+    assert_eq!(simulated.lut().unwrap(), lut.resample(size));
+    assert_eq!(monitor.read_lut(mode).unwrap(), lut.resample(size));
+    assert!(monitor.verify_lut(&lut, mode).unwrap());
+}
+
+#[test]
+fn test_upload_block() {
+    let size = LutSize::uniform(40, 12).unwrap();
+    let simulated = SimulatedMonitor::new()
+        .with_capabilities(BLOCK_LUT_CAPABILITIES)
+        .with_lut(size);
+    let mut monitor = Monitor::with_transport(simulated.clone(), I2C_ADDRESS_DDC_CI);
+    let mode = monitor.lut_mode().unwrap();
+    assert_eq!(mode, LutMode::Block);
+    let lut = GammaLut::from_curves(size, &[0.0, 0.9], &[0.0, 1.0], &[0.1, 1.0]).unwrap();
+    monitor.upload_lut(&lut, mode).unwrap();
+    assert_eq!(simulated.lut().unwrap(), lut);
+    assert!(monitor.verify_lut(&lut, mode).unwrap());
+    assert!(!monitor.verify_lut(&GammaLut::identity(size), mode).unwrap());
+    // Monitors with Block LUT operations usually support Single Point LUT operations too
+    assert!(monitor.verify_lut(&lut, LutMode::SinglePoint).unwrap());
+}
+
+#[test]
+fn test_lut_mode_error() {
+    // Failing to read the capabilities is reported, rather than falling back to Single Point LUT operations
+    let transport = FaultInjector::scripted(SimulatedMonitor::new(), vec![Some(Fault::Io(-5))]);
+    let mut monitor = Monitor::with_transport(transport, I2C_ADDRESS_DDC_CI);
+    assert!(matches!(monitor.lut_mode(), Err(Error::Io(-5))));
+}
+
+#[test]
+fn test_no_lut() {
+    let mut monitor = Monitor::with_transport(SimulatedMonitor::new(), I2C_ADDRESS_DDC_CI);
+    assert!(monitor.lut_size().is_err());
+    assert!(monitor
+        .upload_lut(
+            &GammaLut::identity(LutSize::uniform(256, 8).unwrap()),
+            LutMode::SinglePoint
+        )
+        .is_err());
+}
@@ -0,0 +1,197 @@
+extern crate ddc_macos;
+
+use ddc::{Ddc, ErrorCode, I2C_ADDRESS_DDC_CI};
+use ddc_macos::codec::Encoder;
+use ddc_macos::transport::{
+    DdcTransport, Exchange, Fault, FaultInjector, Recorder, Replayer, Reply, Session, SimulatedMonitor,
+};
+use ddc_macos::{Error, ErrorKind, Monitor};
+use std::fs::File;
+use std::io::{self, Write};
+use std::path::PathBuf;
+
+fn session_path(name: &str) -> PathBuf {
+    std::env::temp_dir().join(format!("ddc-macos-{}-{}.session", name, std::process::id()))
+}
+
+#[test]
+fn test_record_and_replay() {
+    let path = session_path("replay");
+    let transport = FaultInjector::scripted(SimulatedMonitor::new(), vec![None, None, Some(Fault::Io(-5))]);
+    let mut monitor = Monitor::with_transport(
+        Recorder::new(transport, File::create(&path).unwrap()).unwrap(),
+        I2C_ADDRESS_DDC_CI,
+    );
+    monitor.set_vcp_feature(0x10, 80).unwrap();
+    assert_eq!(monitor.get_vcp_feature(0x10).unwrap().value(), 80);
+    assert!(monitor.get_vcp_feature(0x12).is_err());
+    monitor.into_transport().flush().unwrap();
+
+    let session = Session::load(&path).unwrap();
+    std::fs::remove_file(&path).unwrap();
+    assert_eq!(session.exchanges.len(), 3);
+    assert_eq!(session.exchanges[0].reply, Reply::Data(Vec::new()));
+    assert_eq!(session.exchanges[2].reply, Reply::Io(-5));
+
+    let mut monitor = Monitor::with_transport(Replayer::new(session), I2C_ADDRESS_DDC_CI);
+    monitor.set_vcp_feature(0x10, 80).unwrap();
+    assert_eq!(monitor.get_vcp_feature(0x10).unwrap().value(), 80);
+    assert!(matches!(monitor.get_vcp_feature(0x12), Err(Error::Io(-5))));
+}
+
+#[test]
+fn test_replay_mismatched_request() {
+    let path = session_path("mismatch");
+    let mut monitor = Monitor::with_transport(
+        Recorder::new(SimulatedMonitor::new(), File::create(&path).unwrap()).unwrap(),
+        I2C_ADDRESS_DDC_CI,
+    );
+    monitor.get_vcp_feature(0x10).unwrap();
+    drop(monitor);
+
+    let session = Session::load(&path).unwrap();
+    std::fs::remove_file(&path).unwrap();
+    let mut monitor = Monitor::with_transport(Replayer::new(session), I2C_ADDRESS_DDC_CI);
+    assert!(monitor.get_vcp_feature(0x12).is_err());
+}
+
+#[test]
+fn test_session_round_trip() {
+    let text = "ddc-macos session 1\n\
+                # comment\n\
+                37 40000 51820110ac 6e88020010000064003286\n\
+                37 0 518403100050bc -\n\
+                b7 40000 51820112ae !error:ServiceNotFound:\n\
+                37 40000 51820112ae !error:Invalid:no reply: try again\n";
+    let session = Session::read_from(text.as_bytes()).unwrap();
+    assert_eq!(session.exchanges.len(), 4);
+    assert_eq!(session.exchanges[2].i2c_address, 0xb7);
+    assert_eq!(
+        session.exchanges[2].reply,
+        Reply::Failed {
+            kind: ErrorKind::ServiceNotFound,
+            detail: String::new()
+        }
+    );
+    assert_eq!(
+        session.exchanges[3].reply,
+        Reply::Failed {
+            kind: ErrorKind::Invalid,
+            detail: "no reply: try again".into()
+        }
+    );
+
+    let mut written = Vec::new();
+    session.write_to(&mut written).unwrap();
+    assert!(String::from_utf8_lossy(&written).starts_with("ddc-macos session 1\n"));
+    assert_eq!(Session::read_from(written.as_slice()).unwrap(), session);
+}
+
+#[test]
+fn test_errors_replay_as_recorded() {
+    let errors = [
+        Error::MonitorBusy,
+        Error::Ddc(ErrorCode::InvalidChecksum),
+        Error::Ddc(ErrorCode::Invalid("bad: request".into())),
+        Error::SourceAddressMismatch {
+            expected: 0x6e,
+            actual: 0x50,
+        },
+        Error::OpcodeMismatch {
+            expected: 0x02,
+            actual: 0xe4,
+        },
+        Error::CoreGraphics(1001),
+        Error::ServiceNotFound,
+    ];
+    for error in errors {
+        let reply = Reply::from_error(&error);
+        let session = Session {
+            exchanges: vec![Exchange {
+                i2c_address: 0x37,
+                response_delay: Default::default(),
+                request: vec![0x51, 0x82, 0x01, 0x10, 0xac],
+                reply,
+            }],
+        };
+        let mut written = Vec::new();
+        session.write_to(&mut written).unwrap();
+        let session = Session::read_from(written.as_slice()).unwrap();
+        let replayed = session.exchanges[0].reply.to_error().unwrap();
+        assert_eq!(replayed.kind(), error.kind());
+        assert_eq!(replayed.to_string(), error.to_string());
+    }
+    assert!(Session::read_from("ddc-macos session 1\n37 0 51 !error:Unknown:\n".as_bytes()).is_err());
+}
+
+#[test]
+fn test_replay_monitor_busy() {
+    let path = session_path("busy");
+    let transport = FaultInjector::scripted(SimulatedMonitor::new(), vec![Some(Fault::NullMessage)]);
+    let mut monitor = Monitor::with_transport(
+        Recorder::new(transport, File::create(&path).unwrap()).unwrap(),
+        I2C_ADDRESS_DDC_CI,
+    );
+    let recorded = monitor.get_vcp_feature(0x10).unwrap_err().kind();
+    drop(monitor);
+
+    let session = Session::load(&path).unwrap();
+    std::fs::remove_file(&path).unwrap();
+    let mut monitor = Monitor::with_transport(Replayer::new(session), I2C_ADDRESS_DDC_CI);
+    assert_eq!(monitor.get_vcp_feature(0x10).unwrap_err().kind(), recorded);
+}
+
+/// A writer that accepts the session header, then fails
+#[derive(Debug, Default)]
+struct FullDisk {
+    lines: usize,
+}
+
+impl Write for FullDisk {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        if self.lines > 0 {
+            return Err(io::Error::other("disk full"));
+        }
+        self.lines += buf.iter().filter(|&&byte| byte == b'\n').count();
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        Ok(())
+    }
+}
+
+#[test]
+fn test_recording_failure_keeps_result() {
+    let simulated = SimulatedMonitor::new();
+    let mut recorder = Recorder::new(simulated.clone(), FullDisk::default()).unwrap();
+    // Set VCP Feature 0x10 to 80
+    let mut packet = [0u8; 16];
+    let packet = Encoder::new(I2C_ADDRESS_DDC_CI).encode(&[0x03, 0x10, 0x00, 0x50], &mut packet);
+    let mut out = [0u8; 0];
+    let result = recorder.execute(I2C_ADDRESS_DDC_CI, packet, &mut out, Default::default());
+    assert!(result.unwrap().is_empty());
+    assert_eq!(simulated.feature(0x10).unwrap().value(), 80);
+    let error = recorder.finish().unwrap_err();
+    assert_eq!(error.to_string(), "disk full");
+}
+
+#[test]
+fn test_recording_failure_through_monitor() {
+    let mut monitor = Monitor::with_transport(
+        Recorder::new(SimulatedMonitor::new(), FullDisk::default()).unwrap(),
+        I2C_ADDRESS_DDC_CI,
+    );
+    monitor.set_vcp_feature(0x10, 80).unwrap();
+    assert_eq!(monitor.get_vcp_feature(0x10).unwrap().value(), 80);
+    let mut transport = monitor.into_transport();
+    assert_eq!(transport.flush().unwrap_err().kind(), ErrorKind::Io);
+    // The error is only reported once
+    transport.flush().unwrap();
+}
+
+#[test]
+fn test_session_unsupported_version() {
+    assert!(Session::read_from("ddc-macos session 99\n".as_bytes()).is_err());
+    assert!(Session::read_from("something else\n".as_bytes()).is_err());
+}
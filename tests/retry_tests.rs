@@ -0,0 +1,111 @@
+extern crate ddc_macos;
+
+use ddc::{Ddc, I2C_ADDRESS_DDC_CI};
+use ddc_macos::transport::{Fault, FaultInjector, SimulatedMonitor};
+use ddc_macos::{Error, ErrorKind, Monitor, RetryPolicy, RetryStats};
+use std::time::Duration;
+
+fn monitor_with_faults(script: Vec<Option<Fault>>, retry_policy: RetryPolicy) -> Monitor {
+    let mut monitor = Monitor::with_transport(
+        FaultInjector::scripted(SimulatedMonitor::new(), script),
+        I2C_ADDRESS_DDC_CI,
+    );
+    monitor.set_retry_policy(retry_policy.with_backoff([Duration::from_millis(1)]));
+    monitor
+}
+
+#[test]
+fn test_no_retries_by_default() {
+    let mut monitor = Monitor::with_transport(
+        FaultInjector::scripted(SimulatedMonitor::new(), vec![Some(Fault::BadChecksum)]),
+        I2C_ADDRESS_DDC_CI,
+    );
+    assert_eq!(monitor.retry_policy(), &RetryPolicy::none());
+    assert!(monitor.get_vcp_feature(0x10).is_err());
+    assert_eq!(monitor.retry_stats().failed, 1);
+}
+
+#[test]
+fn test_retry_recovers() {
+    let mut monitor = monitor_with_faults(
+        vec![Some(Fault::BadChecksum), Some(Fault::NullMessage)],
+        RetryPolicy::default(),
+    );
+    assert_eq!(monitor.get_vcp_feature(0x10).unwrap().value(), 50);
+    assert_eq!(
+        monitor.retry_stats(),
+        RetryStats {
+            commands: 1,
+            retries: 2,
+            recovered: 1,
+            failed: 0
+        }
+    );
+}
+
+#[test]
+fn test_retry_gives_up() {
+    let mut monitor = monitor_with_faults(
+        vec![Some(Fault::Io(-1)), Some(Fault::Io(-2)), Some(Fault::Io(-3))],
+        RetryPolicy::default(),
+    );
+    assert!(matches!(monitor.get_vcp_feature(0x10), Err(Error::Io(-3))));
+    assert_eq!(monitor.retry_stats().retries, 2);
+    assert_eq!(monitor.retry_stats().failed, 1);
+    // The script is exhausted, next command goes through
+    assert!(monitor.get_vcp_feature(0x10).is_ok());
+    monitor.reset_retry_stats();
+    assert_eq!(monitor.retry_stats(), RetryStats::default());
+}
+
+#[test]
+fn test_non_retryable_error() {
+    let mut monitor = monitor_with_faults(
+        vec![Some(Fault::BadChecksum)],
+        RetryPolicy::default().with_retryable([ErrorKind::MonitorBusy]),
+    );
+    assert!(monitor.get_vcp_feature(0x10).is_err());
+    assert_eq!(monitor.retry_stats().retries, 0);
+}
+
+#[test]
+fn test_backoff_schedule() {
+    let policy = RetryPolicy::default()
+        .with_max_attempts(5)
+        .with_backoff([Duration::from_millis(10), Duration::from_millis(20)]);
+    assert_eq!(policy.max_attempts(), 5);
+    assert_eq!(policy.backoff(1), Duration::from_millis(10));
+    assert_eq!(policy.backoff(2), Duration::from_millis(20));
+    assert_eq!(policy.backoff(4), Duration::from_millis(20));
+    assert_eq!(RetryPolicy::none().with_backoff([]).backoff(1), Duration::ZERO);
+}
+
+#[test]
+fn test_error_kind() {
+    assert!(RetryPolicy::default().is_retryable(&Error::MonitorBusy));
+    assert!(!RetryPolicy::default().is_retryable(&Error::ServiceNotFound));
+    assert_eq!(
+        Error::Ddc(ddc::ErrorCode::InvalidChecksum).kind(),
+        ErrorKind::InvalidChecksum
+    );
+    assert_eq!(
+        Error::OpcodeMismatch {
+            expected: 0x02,
+            actual: 0xe3
+        }
+        .kind(),
+        ErrorKind::OpcodeMismatch
+    );
+}
+
+#[test]
+fn test_error_kind_names() {
+    assert_eq!(ErrorKind::MonitorBusy.name(), "MonitorBusy");
+    assert_eq!(ErrorKind::Io.to_string(), "Io");
+    assert_eq!(
+        "InvalidChecksum".parse::<ErrorKind>().unwrap(),
+        ErrorKind::InvalidChecksum
+    );
+    assert!("invalidchecksum".parse::<ErrorKind>().is_err());
+    assert!("".parse::<ErrorKind>().is_err());
+}
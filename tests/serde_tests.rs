@@ -0,0 +1,55 @@
+#![cfg(feature = "serde")]
+
+extern crate ddc_macos;
+
+use ddc::ErrorCode;
+use ddc_macos::edid::{self, Edid, ValidationReport};
+use ddc_macos::{Capabilities, Error, ErrorKind, InputSource, PowerMode, VcpFeature};
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+use std::fmt::Debug;
+
+const DELL_EDID: &[u8] = include_bytes!("edid/dell.edid.bin");
+const DELL_CAPABILITIES: &[u8] = include_bytes!("capabilities/dell-u2720q.txt");
+
+fn round_trip<T: Serialize + DeserializeOwned + PartialEq + Debug>(value: &T) -> String {
+    let json = serde_json::to_string(value).unwrap();
+    assert_eq!(&serde_json::from_str::<T>(&json).unwrap(), value);
+    json
+}
+
+#[test]
+fn test_error_kind() {
+    let kind = Error::from(ErrorCode::InvalidChecksum).kind();
+    assert_eq!(round_trip(&kind), "\"InvalidChecksum\"");
+    assert_eq!(serde_json::from_str::<ErrorKind>("\"Io\"").unwrap(), ErrorKind::Io);
+    // Serialized names are the explicit kind names
+    assert_eq!(
+        round_trip(&ErrorKind::MonitorBusy),
+        format!("\"{}\"", ErrorKind::MonitorBusy.name())
+    );
+}
+
+#[test]
+fn test_vcp_values() {
+    assert_eq!(round_trip(&VcpFeature::Luminance), "\"Luminance\"");
+    assert_eq!(round_trip(&InputSource::Hdmi1), "\"Hdmi1\"");
+    assert_eq!(round_trip(&InputSource::Other(0x42)), "{\"Other\":66}");
+    round_trip(&PowerMode::Standby);
+    round_trip(&VcpFeature::ALL.to_vec());
+}
+
+#[test]
+fn test_edid() {
+    let edid = Edid::parse(DELL_EDID).unwrap();
+    round_trip(&edid);
+    let report: ValidationReport = edid::validate(DELL_EDID);
+    round_trip(&report);
+}
+
+#[test]
+fn test_capabilities() {
+    let capabilities = Capabilities::parse(DELL_CAPABILITIES);
+    let json = round_trip(&capabilities);
+    assert!(json.contains("\"mccs_version\":{\"major\":2,\"minor\":1}"));
+}
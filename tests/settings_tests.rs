@@ -0,0 +1,71 @@
+extern crate ddc_macos;
+
+use ddc::I2C_ADDRESS_DDC_CI;
+use ddc_macos::transport::SimulatedMonitor;
+use ddc_macos::{FactoryReset, Monitor, VcpFeature};
+use std::time::{Duration, Instant};
+
+#[test]
+fn test_save_settings() {
+    let simulated = SimulatedMonitor::new();
+    let mut monitor = Monitor::with_transport(simulated.clone(), I2C_ADDRESS_DDC_CI);
+    monitor.set_feature(VcpFeature::Luminance, 10).unwrap();
+    let start = Instant::now();
+    monitor.save_settings().unwrap();
+    assert!(start.elapsed() >= Duration::from_millis(200));
+    assert_eq!(simulated.settings_saved(), 1);
+}
+
+#[test]
+fn test_restore_luminance_contrast() {
+    let simulated = SimulatedMonitor::new().with_feature(0x16, 40, 100);
+    let mut monitor = Monitor::with_transport(simulated.clone(), I2C_ADDRESS_DDC_CI);
+    monitor.set_feature(VcpFeature::Luminance, 10).unwrap();
+    monitor.set_feature(VcpFeature::RedGain, 90).unwrap();
+
+    let start = Instant::now();
+    let restored = monitor.restore_factory(FactoryReset::LuminanceContrast).unwrap();
+    assert!(start.elapsed() >= FactoryReset::LuminanceContrast.delay());
+    assert_eq!(restored.len(), 2);
+    assert_eq!(restored[0].feature, VcpFeature::Luminance);
+    assert_eq!((restored[0].before, restored[0].after), (10, 50));
+    assert!(restored[0].changed());
+    assert!(!restored[1].changed());
+    // Color is left alone
+    assert_eq!(simulated.feature(0x16).unwrap().value(), 90);
+}
+
+#[test]
+fn test_restore_color_skips_unsupported_features() {
+    let simulated = SimulatedMonitor::new().with_feature(0x16, 40, 100);
+    let mut monitor = Monitor::with_transport(simulated.clone(), I2C_ADDRESS_DDC_CI);
+    monitor.set_feature(VcpFeature::RedGain, 90).unwrap();
+    monitor.set_feature(VcpFeature::Luminance, 10).unwrap();
+    let restored = monitor.restore_factory(FactoryReset::Color).unwrap();
+    assert_eq!(restored.len(), 1);
+    assert_eq!(restored[0].feature, VcpFeature::RedGain);
+    assert_eq!(restored[0].after, 40);
+    assert_eq!(simulated.feature(0x10).unwrap().value(), 10);
+}
+
+#[test]
+fn test_restore_all() {
+    let simulated = SimulatedMonitor::new();
+    let mut monitor = Monitor::with_transport(simulated.clone(), I2C_ADDRESS_DDC_CI);
+    monitor.set_feature(VcpFeature::Contrast, 5).unwrap();
+    monitor.set_feature(VcpFeature::AudioVolume, 80).unwrap();
+    let restored = monitor.restore_factory(FactoryReset::All).unwrap();
+    assert!(restored
+        .iter()
+        .any(|feature| feature.feature == VcpFeature::Contrast && feature.after == 75));
+    assert_eq!(simulated.feature(0x62).unwrap().value(), 30);
+}
+
+#[test]
+fn test_reset_features() {
+    assert_eq!(FactoryReset::All.feature().code(), 0x04);
+    assert_eq!(FactoryReset::LuminanceContrast.feature().code(), 0x05);
+    assert_eq!(FactoryReset::Geometry.feature().code(), 0x06);
+    assert_eq!(FactoryReset::Color.feature().code(), 0x08);
+    assert!(FactoryReset::Geometry.affected_features().is_empty());
+}
@@ -0,0 +1,89 @@
+extern crate ddc_macos;
+
+use ddc::{Ddc, I2C_ADDRESS_DDC_CI};
+use ddc_macos::transport::SimulatedMonitor;
+use ddc_macos::Monitor;
+use std::time::{Duration, Instant};
+
+#[test]
+fn test_get_vcp_feature() {
+    let mut monitor = Monitor::with_transport(SimulatedMonitor::new(), I2C_ADDRESS_DDC_CI);
+    let value = monitor.get_vcp_feature(0x10).unwrap();
+    assert_eq!(value.value(), 50);
+    assert_eq!(value.maximum(), 100);
+}
+
+#[test]
+fn test_get_unsupported_vcp_feature() {
+    let mut monitor = Monitor::with_transport(SimulatedMonitor::new(), I2C_ADDRESS_DDC_CI);
+    assert!(monitor.get_vcp_feature(0x8d).is_err());
+}
+
+#[test]
+fn test_set_vcp_feature() {
+    let simulated = SimulatedMonitor::new();
+    let mut monitor = Monitor::with_transport(simulated.clone(), I2C_ADDRESS_DDC_CI);
+    monitor.set_vcp_feature(0x10, 20).unwrap();
+    assert_eq!(simulated.feature(0x10).unwrap().value(), 20);
+    assert_eq!(monitor.get_vcp_feature(0x10).unwrap().value(), 20);
+}
+
+#[test]
+fn test_set_read_only_vcp_feature() {
+    let simulated = SimulatedMonitor::new().with_read_only_feature(0x10, 50, 100);
+    let mut monitor = Monitor::with_transport(simulated.clone(), I2C_ADDRESS_DDC_CI);
+    monitor.set_vcp_feature(0x10, 20).unwrap();
+    assert_eq!(simulated.feature(0x10).unwrap().value(), 50);
+}
+
+#[test]
+fn test_capabilities_string() {
+    let capabilities =
+        "(prot(monitor)type(lcd)model(TEST)cmds(01 02 03 F3)vcp(10 12 14(05 06 08) 60(0F 11))mccs_ver(2.1))";
+    assert!(capabilities.len() > 64);
+    let mut monitor = Monitor::with_transport(
+        SimulatedMonitor::new().with_capabilities(capabilities),
+        I2C_ADDRESS_DDC_CI,
+    );
+    assert_eq!(monitor.capabilities_string().unwrap(), capabilities.as_bytes());
+}
+
+#[test]
+fn test_timing_report() {
+    let mut monitor = Monitor::with_transport(
+        SimulatedMonitor::new().with_timing_report(0x83, 6750, 6000),
+        I2C_ADDRESS_DDC_CI,
+    );
+    let timing = monitor.get_timing_report().unwrap();
+    assert_eq!(timing.timing_status, 0x83);
+    assert_eq!(timing.horizontal_frequency, 6750);
+    assert_eq!(timing.vertical_frequency, 6000);
+}
+
+#[test]
+fn test_mdcp29xx_address() {
+    let mut monitor = Monitor::with_transport(SimulatedMonitor::new(), 0xb7);
+    assert_eq!(monitor.get_vcp_feature(0x12).unwrap().value(), 75);
+}
+
+#[test]
+fn test_latency() {
+    let mut monitor = Monitor::with_transport(
+        SimulatedMonitor::new().with_latency(Duration::from_millis(20)),
+        I2C_ADDRESS_DDC_CI,
+    );
+    let start = Instant::now();
+    monitor.get_vcp_feature(0x10).unwrap();
+    assert!(start.elapsed() >= Duration::from_millis(20));
+}
+
+#[test]
+fn test_unknown_request_is_answered_with_null_message() {
+    use ddc::DdcCommandRaw;
+    let mut monitor = Monitor::with_transport(SimulatedMonitor::new(), I2C_ADDRESS_DDC_CI);
+    let mut out = [0u8; 16];
+    assert!(matches!(
+        monitor.execute_raw(&[0xee], &mut out, Duration::ZERO),
+        Err(ddc_macos::Error::MonitorBusy)
+    ));
+}
@@ -0,0 +1,121 @@
+extern crate ddc_macos;
+
+use ddc::I2C_ADDRESS_DDC_CI;
+use ddc_macos::transport::{DdcTransport, Fault, FaultInjector, SimulatedMonitor};
+use ddc_macos::{Error, Monitor, RetryPolicy};
+use std::time::Duration;
+
+fn table(len: usize) -> Vec<u8> {
+    (0..len).map(|i| (i * 7) as u8).collect()
+}
+
+/// Answers one request with the reply to the request before it, like a monitor returning a stale reply
+#[derive(Debug)]
+struct StaleReply {
+    inner: SimulatedMonitor,
+    stale_request: usize,
+    requests: usize,
+    previous: Vec<u8>,
+}
+
+impl DdcTransport for StaleReply {
+    fn execute<'a>(
+        &mut self,
+        i2c_address: u16,
+        packet: &[u8],
+        out: &'a mut [u8],
+        response_delay: Duration,
+    ) -> Result<&'a mut [u8], Error> {
+        self.requests += 1;
+        let packet = if self.requests == self.stale_request {
+            self.previous.clone()
+        } else {
+            packet.to_vec()
+        };
+        self.previous = packet.clone();
+        self.inner.execute(i2c_address, &packet, out, response_delay)
+    }
+}
+
+#[test]
+fn test_read_table() {
+    for len in [0, 1, 32, 33, 100] {
+        let simulated = SimulatedMonitor::new().with_table(0x73, table(len));
+        let mut monitor = Monitor::with_transport(simulated, I2C_ADDRESS_DDC_CI);
+        assert_eq!(monitor.read_table(0x73).unwrap(), table(len));
+        // One request per fragment, and one for the final empty fragment
+        assert_eq!(
+            monitor.retry_stats().commands as usize,
+            len / 32 + 1 + (len % 32 != 0) as usize
+        );
+    }
+}
+
+#[test]
+fn test_read_unsupported_table() {
+    let mut monitor = Monitor::with_transport(SimulatedMonitor::new(), I2C_ADDRESS_DDC_CI);
+    assert!(matches!(monitor.read_table(0x73), Err(Error::MonitorBusy)));
+}
+
+#[test]
+fn test_write_table() {
+    let simulated = SimulatedMonitor::new().with_table(0x74, vec![0xff; 4]);
+    let mut monitor = Monitor::with_transport(simulated.clone(), I2C_ADDRESS_DDC_CI);
+    monitor.write_table(0x74, 2, &table(70)).unwrap();
+    assert_eq!(monitor.retry_stats().commands, 3);
+    let mut expected = vec![0xff, 0xff];
+    expected.extend(table(70));
+    assert_eq!(simulated.table(0x74).unwrap(), expected);
+    assert_eq!(monitor.read_table(0x74).unwrap(), expected);
+}
+
+#[test]
+fn test_write_table_too_long() {
+    let mut monitor = Monitor::with_transport(SimulatedMonitor::new(), I2C_ADDRESS_DDC_CI);
+    assert!(monitor.write_table(0x74, 0xfff0, &[0; 32]).is_err());
+}
+
+#[test]
+fn test_fragment_retried() {
+    let simulated = SimulatedMonitor::new().with_table(0x73, table(100));
+    let faults = vec![None, Some(Fault::BadChecksum), None, Some(Fault::Truncate(4))];
+    let mut monitor = Monitor::with_transport(FaultInjector::scripted(simulated, faults), I2C_ADDRESS_DDC_CI);
+    monitor.set_retry_policy(RetryPolicy::default().with_backoff([Duration::from_millis(1)]));
+    assert_eq!(monitor.read_table(0x73).unwrap(), table(100));
+    let stats = monitor.retry_stats();
+    assert_eq!((stats.commands, stats.retries, stats.recovered), (5, 2, 2));
+}
+
+#[test]
+fn test_fragment_not_retried_by_default() {
+    let simulated = SimulatedMonitor::new().with_table(0x73, table(100));
+    let faults = vec![None, Some(Fault::BadChecksum)];
+    let mut monitor = Monitor::with_transport(FaultInjector::scripted(simulated, faults), I2C_ADDRESS_DDC_CI);
+    assert!(monitor.read_table(0x73).is_err());
+}
+
+#[test]
+fn test_stale_fragment_retried() {
+    let transport = StaleReply {
+        inner: SimulatedMonitor::new().with_table(0x73, table(100)),
+        stale_request: 3,
+        requests: 0,
+        previous: Vec::new(),
+    };
+    let mut monitor = Monitor::with_transport(transport, I2C_ADDRESS_DDC_CI);
+    assert!(matches!(
+        monitor.read_table(0x73),
+        Err(Error::Ddc(ddc::ErrorCode::InvalidOffset))
+    ));
+
+    let transport = StaleReply {
+        inner: SimulatedMonitor::new().with_table(0x73, table(100)),
+        stale_request: 3,
+        requests: 0,
+        previous: Vec::new(),
+    };
+    let mut monitor = Monitor::with_transport(transport, I2C_ADDRESS_DDC_CI);
+    monitor.set_retry_policy(RetryPolicy::default().with_backoff([Duration::from_millis(1)]));
+    assert_eq!(monitor.read_table(0x73).unwrap(), table(100));
+    assert_eq!(monitor.retry_stats().recovered, 1);
+}
@@ -0,0 +1,30 @@
+extern crate ddc_macos;
+
+use ddc::I2C_ADDRESS_DDC_CI;
+use ddc_macos::transport::SimulatedMonitor;
+use ddc_macos::{Monitor, TimingReport};
+
+#[test]
+fn test_decode_status() {
+    let report = TimingReport::new(0xc2, 6750, 6000);
+    assert!(report.out_of_range);
+    assert!(report.unstable_count);
+    assert!(report.horizontal_sync_positive);
+    assert!(!report.vertical_sync_positive);
+    assert_eq!(report.status, 0xc2);
+    assert!((report.horizontal_frequency - 67.5).abs() < 1e-9);
+    assert!((report.vertical_frequency - 60.0).abs() < 1e-9);
+}
+
+#[test]
+fn test_timing_report() {
+    let simulated = SimulatedMonitor::new().with_timing_report(0x01, 13_500, 14_385);
+    let mut monitor = Monitor::with_transport(simulated, I2C_ADDRESS_DDC_CI);
+    let report = monitor.timing_report().unwrap();
+    assert!(!report.out_of_range);
+    assert!(!report.unstable_count);
+    assert!(!report.horizontal_sync_positive);
+    assert!(report.vertical_sync_positive);
+    assert!((report.horizontal_frequency - 135.0).abs() < 1e-9);
+    assert!((report.vertical_frequency - 143.85).abs() < 1e-9);
+}
@@ -0,0 +1,161 @@
+extern crate ddc_macos;
+
+use ddc::I2C_ADDRESS_DDC_CI;
+use ddc_macos::transport::SimulatedMonitor;
+use ddc_macos::{run_transitions, CancelToken, Easing, Monitor, Transition, VcpFeature, MIN_STEP_INTERVAL};
+use std::time::Duration;
+
+#[test]
+fn test_easing() {
+    for easing in [Easing::Linear, Easing::EaseIn, Easing::EaseOut, Easing::EaseInOut] {
+        assert_eq!(easing.apply(0.0), 0.0);
+        assert_eq!(easing.apply(1.0), 1.0);
+        assert_eq!(easing.apply(2.0), 1.0);
+        let samples: Vec<f64> = (0..=10).map(|t| easing.apply(t as f64 / 10.0)).collect();
+        assert!(samples.windows(2).all(|pair| pair[0] <= pair[1]));
+    }
+    assert!(Easing::EaseIn.apply(0.5) < 0.5);
+    assert!(Easing::EaseOut.apply(0.5) > 0.5);
+    assert_eq!(Easing::EaseInOut.apply(0.5), 0.5);
+}
+
+#[test]
+fn test_transition() {
+    let simulated = SimulatedMonitor::new().with_feature(0x10, 80, 100);
+    let mut monitor = Monitor::with_transport(simulated.clone(), I2C_ADDRESS_DDC_CI);
+    let duration = Duration::from_millis(500);
+    let report = monitor
+        .transition(Transition::new(VcpFeature::Luminance, 20, duration).with_easing(Easing::Linear))
+        .unwrap();
+    assert_eq!((report.from, report.to, report.reached), (80, 20, 20));
+    assert!(!report.cancelled);
+    assert!(report.steps > 1);
+    assert!(report.steps as u128 <= duration.as_millis() / MIN_STEP_INTERVAL.as_millis() + 1);
+    assert!(report.elapsed >= duration);
+    assert_eq!(simulated.feature(0x10).unwrap().value(), 20);
+}
+
+#[test]
+fn test_target_limited_to_maximum() {
+    let simulated = SimulatedMonitor::new();
+    let mut monitor = Monitor::with_transport(simulated.clone(), I2C_ADDRESS_DDC_CI);
+    let report = monitor
+        .transition(Transition::new(VcpFeature::Luminance, 500, Duration::ZERO))
+        .unwrap();
+    assert_eq!((report.to, report.reached, report.steps), (100, 100, 1));
+    let report = monitor
+        .transition(Transition::new(VcpFeature::Luminance, 100, Duration::from_secs(10)))
+        .unwrap();
+    assert_eq!(report.steps, 0);
+    assert!(report.elapsed < Duration::from_secs(1));
+}
+
+#[test]
+fn test_adapts_to_slow_monitor() {
+    let latency = Duration::from_millis(100);
+    let simulated = SimulatedMonitor::new().with_latency(latency);
+    let mut monitor = Monitor::with_transport(simulated, I2C_ADDRESS_DDC_CI);
+    let duration = Duration::from_millis(1000);
+    let report = monitor
+        .transition(Transition::new(VcpFeature::Luminance, 0, duration).with_easing(Easing::Linear))
+        .unwrap();
+    assert_eq!(report.reached, 0);
+    // Each step takes at least the latency, and waits as long before the next one
+    assert!(report.steps as u128 <= duration.as_millis() / (2 * latency.as_millis()) + 1);
+}
+
+#[test]
+fn test_cancel() {
+    let simulated = SimulatedMonitor::new();
+    let mut monitor = Monitor::with_transport(simulated.clone(), I2C_ADDRESS_DDC_CI);
+    let cancel = CancelToken::new();
+    cancel.cancel();
+    let report = monitor
+        .transition(Transition::new(VcpFeature::Luminance, 0, Duration::from_secs(1)).with_cancel(cancel))
+        .unwrap();
+    assert!(report.cancelled);
+    assert_eq!((report.steps, report.reached), (0, 50));
+
+    let cancel = CancelToken::new();
+    let canceller = {
+        let cancel = cancel.clone();
+        std::thread::spawn(move || {
+            std::thread::sleep(Duration::from_millis(300));
+            cancel.cancel();
+        })
+    };
+    let report = monitor
+        .transition(
+            Transition::new(VcpFeature::Luminance, 0, Duration::from_secs(5))
+                .with_easing(Easing::Linear)
+                .with_cancel(cancel),
+        )
+        .unwrap();
+    canceller.join().unwrap();
+    assert!(report.cancelled);
+    assert!(report.elapsed < Duration::from_secs(1));
+    assert!(report.reached > 0 && report.reached < 50);
+    assert_eq!(simulated.feature(0x10).unwrap().value(), report.reached);
+}
+
+#[test]
+fn test_non_continuous_feature() {
+    let mut monitor = Monitor::with_transport(SimulatedMonitor::new(), I2C_ADDRESS_DDC_CI);
+    assert!(monitor
+        .transition(Transition::new(VcpFeature::InputSource, 0x11, Duration::ZERO))
+        .is_err());
+}
+
+#[test]
+fn test_parallel_transitions() {
+    let first = SimulatedMonitor::new();
+    let second = SimulatedMonitor::new().with_latency(Duration::from_millis(20));
+    let mut first_monitor = Monitor::with_transport(first.clone(), I2C_ADDRESS_DDC_CI);
+    let mut second_monitor = Monitor::with_transport(second.clone(), I2C_ADDRESS_DDC_CI);
+    let mut failing_monitor = Monitor::with_transport(SimulatedMonitor::empty(), I2C_ADDRESS_DDC_CI);
+    let duration = Duration::from_millis(500);
+    let reports = run_transitions(&mut [
+        (
+            &mut first_monitor,
+            Transition::new(VcpFeature::Luminance, 100, duration),
+        ),
+        (&mut second_monitor, Transition::new(VcpFeature::Contrast, 0, duration)),
+        (
+            &mut failing_monitor,
+            Transition::new(VcpFeature::Luminance, 0, duration),
+        ),
+    ]);
+    assert_eq!(reports.len(), 3);
+    let first_report = reports[0].as_ref().unwrap();
+    let second_report = reports[1].as_ref().unwrap();
+    assert!(reports[2].is_err());
+    assert_eq!(first.feature(0x10).unwrap().value(), 100);
+    assert_eq!(second.feature(0x12).unwrap().value(), 0);
+    // Both ran over the same period of time, not one after the other
+    assert!(first_report.elapsed < duration * 2);
+    assert!(second_report.elapsed < duration * 2);
+}
+
+#[test]
+fn test_slow_monitor_does_not_hold_back_others() {
+    let fast = SimulatedMonitor::new();
+    let slow = SimulatedMonitor::new().with_latency(Duration::from_millis(200));
+    let mut fast_monitor = Monitor::with_transport(fast, I2C_ADDRESS_DDC_CI);
+    let mut slow_monitor = Monitor::with_transport(slow, I2C_ADDRESS_DDC_CI);
+    let duration = Duration::from_millis(500);
+    let reports = run_transitions(&mut [
+        (
+            &mut fast_monitor,
+            Transition::new(VcpFeature::Luminance, 100, duration).with_easing(Easing::Linear),
+        ),
+        (
+            &mut slow_monitor,
+            Transition::new(VcpFeature::Luminance, 100, duration).with_easing(Easing::Linear),
+        ),
+    ]);
+    let fast_report = reports[0].as_ref().unwrap();
+    let slow_report = reports[1].as_ref().unwrap();
+    // Steps of the fast monitor are not delayed by the commands of the slow one
+    assert!(fast_report.steps >= 6, "{} steps", fast_report.steps);
+    assert!(slow_report.steps < fast_report.steps);
+}
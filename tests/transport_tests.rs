@@ -0,0 +1,52 @@
+extern crate ddc_macos;
+
+use ddc::Ddc;
+use ddc_macos::{DdcTransport, Error, Monitor};
+use std::time::Duration;
+
+/// Transport that answers every request with a fixed "Get VCP feature" reply.
+#[derive(Debug, Default)]
+struct FixedReply;
+
+impl DdcTransport for FixedReply {
+    fn execute<'a>(
+        &mut self,
+        _i2c_address: u16,
+        _packet: &[u8],
+        out: &'a mut [u8],
+        _response_delay: Duration,
+    ) -> Result<&'a mut [u8], Error> {
+        if out.is_empty() {
+            return Ok(out);
+        }
+        let reply = [0x02, 0x00, 0x10, 0x00, 0x00, 0x64, 0x00, 0x32];
+        out[0] = 0x6e;
+        out[1] = 0x80 | reply.len() as u8;
+        out[2..2 + reply.len()].copy_from_slice(&reply);
+        out[2 + reply.len()] = out[..2 + reply.len()].iter().fold(0x50, |sum, v| sum ^ v);
+        Ok(&mut out[..3 + reply.len()])
+    }
+}
+
+#[test]
+fn test_get_vcp_feature_over_transport() {
+    let mut monitor = Monitor::with_transport(FixedReply, ddc::I2C_ADDRESS_DDC_CI);
+    let value = monitor.get_vcp_feature(0x10).unwrap();
+    assert_eq!(value.value(), 0x32);
+    assert_eq!(value.maximum(), 0x64);
+}
+
+#[test]
+fn test_set_vcp_feature_over_transport() {
+    let mut monitor = Monitor::with_transport(FixedReply, ddc::I2C_ADDRESS_DDC_CI);
+    assert!(monitor.set_vcp_feature(0x10, 0x20).is_ok());
+}
+
+#[test]
+fn test_monitor_on_another_thread() {
+    let mut monitor = Monitor::with_transport(FixedReply, ddc::I2C_ADDRESS_DDC_CI);
+    let value = std::thread::spawn(move || monitor.get_vcp_feature(0x10).unwrap().value())
+        .join()
+        .unwrap();
+    assert_eq!(value, 0x32);
+}
@@ -0,0 +1,139 @@
+extern crate ddc_macos;
+
+use ddc_macos::edid::{validate, Edid, Issue, Location, Severity, BLOCK_LEN};
+
+const DELL_EDID: &[u8] = include_bytes!("edid/dell.edid.bin");
+
+fn fix_checksum(block: &mut [u8]) {
+    let sum = block[..BLOCK_LEN - 1]
+        .iter()
+        .fold(0u8, |sum, byte| sum.wrapping_add(*byte));
+    block[BLOCK_LEN - 1] = 0u8.wrapping_sub(sum);
+}
+
+#[test]
+fn test_valid_edid() {
+    let report = validate(DELL_EDID);
+    assert_eq!(report.findings, []);
+    assert!(report.is_valid());
+    assert_eq!(report.max_severity(), None);
+    assert_eq!(report.repaired, None);
+}
+
+#[test]
+fn test_too_short() {
+    let report = validate(&DELL_EDID[..100]);
+    assert!(!report.is_valid());
+    assert_eq!(report.findings[0].issue, Issue::TooShort(100));
+    assert_eq!(report.repaired, None);
+}
+
+#[test]
+fn test_bad_checksum_is_repaired() {
+    let mut data = DELL_EDID.to_vec();
+    data[BLOCK_LEN + 10] ^= 0x01;
+    let report = validate(&data);
+    assert!(!report.is_valid());
+    assert_eq!(report.findings.len(), 1);
+    let finding = &report.findings[0];
+    assert_eq!(finding.severity, Severity::Error);
+    assert_eq!(finding.location, Location::Block(1));
+    assert!(matches!(finding.issue, Issue::InvalidChecksum { .. }));
+    assert_eq!(finding.to_string().split(':').next(), Some("error"));
+
+    let repaired = report.repaired.unwrap();
+    assert_eq!(repaired[BLOCK_LEN + 10], data[BLOCK_LEN + 10]);
+    assert!(validate(&repaired).is_valid());
+}
+
+#[test]
+fn test_corrupt_header_is_repaired() {
+    let mut data = DELL_EDID.to_vec();
+    data[0] = 0xff;
+    let report = validate(&data);
+    let issues: Vec<_> = report.findings.iter().map(|finding| &finding.issue).collect();
+    assert_eq!(issues[0], &Issue::InvalidHeader);
+    assert!(matches!(issues[1], Issue::InvalidChecksum { .. }));
+    let repaired = report.repaired.unwrap();
+    assert_eq!(repaired, DELL_EDID);
+    assert!(Edid::parse(&repaired).is_ok());
+}
+
+#[test]
+fn test_missing_extension() {
+    // A dock that only forwards the base block and the first extension
+    let report = validate(&DELL_EDID[..2 * BLOCK_LEN]);
+    assert_eq!(
+        report.findings[0].issue,
+        Issue::ExtensionCountMismatch {
+            declared: 2,
+            present: 1
+        }
+    );
+    assert_eq!(report.findings[0].severity, Severity::Error);
+    let repaired = report.repaired.unwrap();
+    assert_eq!(repaired[126], 1);
+    assert_eq!(validate(&repaired).findings, []);
+}
+
+#[test]
+fn test_trailing_bytes() {
+    let mut data = DELL_EDID.to_vec();
+    data.extend_from_slice(&[0; 7]);
+    let report = validate(&data);
+    assert!(report.is_valid());
+    assert_eq!(report.max_severity(), Some(Severity::Warning));
+    assert_eq!(report.findings[0].issue, Issue::TrailingBytes(7));
+    assert_eq!(report.repaired.unwrap(), DELL_EDID);
+}
+
+#[test]
+fn test_descriptor_sanity() {
+    let mut data = DELL_EDID[..BLOCK_LEN].to_vec();
+    data[126] = 0;
+    // Product name descriptor with a control character
+    let name = (54..126).step_by(18).find(|&offset| data[offset + 3] == 0xfc).unwrap();
+    data[name + 6] = 0x07;
+    // Another display descriptor turned into range limits with a minimum vertical rate above the maximum
+    let limits = (54..126)
+        .step_by(18)
+        .find(|&offset| offset != name && data[offset] == 0)
+        .unwrap();
+    data[limits..limits + 18].copy_from_slice(&[
+        0, 0, 0, 0xfd, 0, 120, 48, 30, 160, 100, 0x0a, 0x20, 0x20, 0x20, 0x20, 0x20, 0x20, 0x20,
+    ]);
+    fix_checksum(&mut data);
+
+    let report = validate(&data);
+    assert!(report.is_valid());
+    assert_eq!(report.repaired, None);
+    let issues: Vec<_> = report
+        .findings_at_least(Severity::Warning)
+        .map(|finding| finding.issue.clone())
+        .collect();
+    assert!(issues.contains(&Issue::InvalidDescriptorString));
+    assert!(issues.contains(&Issue::InvalidRangeLimits));
+}
+
+#[test]
+fn test_missing_preferred_timing() {
+    let mut data = DELL_EDID[..BLOCK_LEN].to_vec();
+    data[126] = 0;
+    data[54..72].copy_from_slice(&[0, 0, 0, 0x10, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0]);
+    fix_checksum(&mut data);
+    let report = validate(&data);
+    assert_eq!(report.findings.len(), 1);
+    assert_eq!(report.findings[0].location, Location::Descriptor(0));
+    assert_eq!(report.findings[0].issue, Issue::MissingPreferredTiming);
+}
+
+#[test]
+fn test_unknown_extension() {
+    let mut data = DELL_EDID.to_vec();
+    data[2 * BLOCK_LEN] = 0x42;
+    fix_checksum(&mut data[2 * BLOCK_LEN..]);
+    let report = validate(&data);
+    assert!(report.is_valid());
+    assert_eq!(report.max_severity(), Some(Severity::Info));
+    assert_eq!(report.findings[0].issue, Issue::UnknownExtension(0x42));
+}
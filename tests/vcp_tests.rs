@@ -0,0 +1,104 @@
+extern crate ddc_macos;
+
+use ddc::{Ddc, I2C_ADDRESS_DDC_CI};
+use ddc_macos::transport::SimulatedMonitor;
+use ddc_macos::{
+    AudioMute, ColorPreset, FeatureAccess, FeatureKind, FeatureValue, InputSource, Monitor, PowerMode, VcpFeature,
+};
+
+#[test]
+fn test_feature_codes() {
+    assert_eq!(VcpFeature::Luminance.code(), 0x10);
+    assert_eq!(u8::from(VcpFeature::InputSource), 0x60);
+    assert_eq!(VcpFeature::from_code(0xd6), Some(VcpFeature::PowerMode));
+    assert_eq!(VcpFeature::from_code(0xe0), None);
+    for feature in VcpFeature::ALL {
+        assert_eq!(VcpFeature::from_code(feature.code()), Some(feature));
+    }
+    let codes: Vec<_> = VcpFeature::ALL.iter().map(|feature| feature.code()).collect();
+    assert!(codes.windows(2).all(|pair| pair[0] < pair[1]));
+}
+
+#[test]
+fn test_feature_metadata() {
+    assert_eq!(VcpFeature::Luminance.to_string(), "Luminance");
+    assert_eq!(VcpFeature::InputSource.name(), "Input Select");
+    assert!(!VcpFeature::Contrast.description().is_empty());
+    assert_eq!(VcpFeature::Luminance.kind(), FeatureKind::Continuous);
+    assert_eq!(VcpFeature::InputSource.kind(), FeatureKind::NonContinuous);
+    assert_eq!(VcpFeature::VcpVersion.access(), FeatureAccess::ReadOnly);
+    assert_eq!(VcpFeature::RestoreFactoryDefaults.access(), FeatureAccess::WriteOnly);
+}
+
+#[test]
+fn test_input_source_values() {
+    assert_eq!(InputSource::from_value(0x11), InputSource::Hdmi1);
+    assert_eq!(InputSource::from_value(0x0f), InputSource::DisplayPort1);
+    assert_eq!(InputSource::from_value(0x1b), InputSource::Other(0x1b));
+    assert_eq!(InputSource::from_vcp(0x0112), InputSource::Hdmi2);
+    assert_eq!(InputSource::Hdmi1.to_string(), "HDMI 1");
+    assert_eq!(InputSource::Other(0x1b).to_string(), "Input 0x1b");
+    for input in InputSource::ALL {
+        assert_eq!(InputSource::from_value(input.value()), input);
+    }
+}
+
+#[test]
+fn test_color_preset_values() {
+    assert_eq!(ColorPreset::from_vcp(0x05), ColorPreset::Temperature(6500));
+    assert_eq!(ColorPreset::from_vcp(0x0c), ColorPreset::User(2));
+    for value in 0x01..=0x0d {
+        assert_eq!(ColorPreset::from_vcp(value).to_vcp().unwrap(), value);
+    }
+    assert_eq!(ColorPreset::Temperature(6500).to_string(), "6500 K");
+    assert_eq!(ColorPreset::User(3).to_vcp().unwrap(), 0x0d);
+    assert!(ColorPreset::Temperature(5555).to_vcp().is_err());
+    assert!(ColorPreset::User(0).to_vcp().is_err());
+    assert!(ColorPreset::User(4).to_vcp().is_err());
+}
+
+#[test]
+fn test_power_mode_and_mute_values() {
+    assert_eq!(PowerMode::from_vcp(0x04), PowerMode::Off);
+    assert_eq!(PowerMode::Standby.to_vcp().unwrap(), 0x02);
+    assert_eq!(PowerMode::from_vcp(0x09), PowerMode::Other(0x09));
+    assert_eq!(AudioMute::from_vcp(0x01), AudioMute::Muted);
+    assert_eq!(AudioMute::Unmuted.to_vcp().unwrap(), 0x02);
+    assert_eq!(AudioMute::Muted.to_string(), "Muted");
+    assert_eq!(AudioMute::Other(0x07).to_string(), "Mute 0x07");
+}
+
+#[test]
+fn test_monitor_typed_features() {
+    let simulated = SimulatedMonitor::new();
+    let mut monitor = Monitor::with_transport(simulated.clone(), I2C_ADDRESS_DDC_CI);
+    assert_eq!(monitor.get_feature(VcpFeature::Luminance).unwrap().value(), 50);
+    monitor.set_feature(VcpFeature::Contrast, 40).unwrap();
+    assert_eq!(monitor.get_vcp_feature(0x12).unwrap().value(), 40);
+
+    assert_eq!(monitor.input_source().unwrap(), InputSource::DisplayPort1);
+    monitor.set_input_source(InputSource::Hdmi2).unwrap();
+    assert_eq!(simulated.feature(0x60).unwrap().value(), 0x12);
+
+    assert_eq!(monitor.power_mode().unwrap(), PowerMode::On);
+    monitor.set_power_mode(PowerMode::Standby).unwrap();
+    assert_eq!(monitor.get_value::<PowerMode>().unwrap(), PowerMode::Standby);
+}
+
+#[test]
+fn test_invalid_values_are_not_written() {
+    let simulated = SimulatedMonitor::new().with_feature(0x14, 0x05, 0x0d);
+    let mut monitor = Monitor::with_transport(simulated.clone(), I2C_ADDRESS_DDC_CI);
+    assert!(monitor.set_value(ColorPreset::Temperature(5555)).is_err());
+    assert!(monitor.set_value(ColorPreset::User(0)).is_err());
+    assert_eq!(simulated.feature(0x14).unwrap().value(), 0x05);
+    monitor.set_value(ColorPreset::User(1)).unwrap();
+    assert_eq!(simulated.feature(0x14).unwrap().value(), 0x0b);
+}
+
+#[test]
+fn test_raw_codes_still_work() {
+    let simulated = SimulatedMonitor::new().with_feature(0xe0, 3, 10);
+    let mut monitor = Monitor::with_transport(simulated, I2C_ADDRESS_DDC_CI);
+    assert_eq!(monitor.get_vcp_feature(0xe0).unwrap().value(), 3);
+}